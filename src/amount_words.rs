@@ -0,0 +1,460 @@
+use crate::{ CurrencyAmount, Error, Language, Result, locale::Unit };
+#[cfg(test)]
+use crate::currency_amount::amount;
+
+impl Language {
+    fn and(self) -> &'static str {
+        match self {
+            Language::English => "and",
+            Language::French => "et",
+            Language::German => "und",
+            Language::Spanish => "con",
+        }
+    }
+
+    fn minus(self) -> &'static str {
+        match self {
+            Language::English | Language::German => "minus",
+            Language::French => "moins",
+            Language::Spanish => "menos",
+        }
+    }
+
+    /// `n` in words, as it is written before a noun of the given gender.
+    fn number(self, n: u64, feminine: bool) -> String {
+        match self {
+            Language::English => english(n),
+            Language::French => french(n, feminine),
+            Language::German => german(n),
+            Language::Spanish => spanish(n, feminine),
+        }
+    }
+
+    /// `n` followed by `unit` in the singular or plural, as the number
+    /// requires.
+    fn count(self, n: u64, unit: &Unit) -> String {
+        let singular = match self {
+            Language::French => n <= 1,
+            _ => n == 1,
+        };
+        let noun = if singular { unit.one } else { unit.other };
+
+        // "un million de dollars", "un millón de dólares"
+        let of = match self {
+            Language::French | Language::Spanish if n >= 1_000_000 && n.is_multiple_of(1_000_000) => {
+                if self == Language::French && noun.starts_with(['a', 'e', 'é', 'i', 'o', 'u']) {
+                    "d'"
+                } else {
+                    "de "
+                }
+            }
+            _ => "",
+        };
+        format!("{} {}{}", self.number(n, unit.feminine), of, noun)
+    }
+}
+
+impl CurrencyAmount {
+    /// Spells this amount out, as on a contract: "One hundred twenty-three
+    /// dollars and forty-five cents", "Cent vingt-trois dollars et
+    /// quarante-cinq cents". The minor part is left out when it is zero.
+    ///
+    /// Unit names come from the [`Language`]'s currency names, which cover a
+    /// small set of common currencies; others fail with
+    /// [`Error::NoCurrencyWords`]. The amount must be a whole number of
+    /// minor units, so that nothing is rounded away: see
+    /// [`CurrencyAmount::to_minor_units`].
+    pub fn to_words(&self, language: Language) -> Result<String> {
+        self.words(language, false)
+    }
+
+    /// Spells this amount out as on a cheque, with the minor part as a
+    /// fraction of the major unit: "One hundred twenty-three dollars and
+    /// 45/100". Currencies without minor units, like JPY, have no fraction.
+    pub fn to_cheque_words(&self, language: Language) -> Result<String> {
+        self.words(language, true)
+    }
+
+    fn words(&self, language: Language, cheque: bool) -> Result<String> {
+        let units = self.to_minor_units()?;
+        // `to_minor_units` has succeeded, so the currency has minor units.
+        let minor_units = self.currency().minor_units().unwrap_or(0);
+        let scale = 10u64.checked_pow(minor_units as u32).ok_or(Error::MantissaOverflow)?;
+        let (major, minor) = (units.unsigned_abs() / scale, units.unsigned_abs() % scale);
+
+        let names = language.names(self.currency().code()).ok_or_else(|| Error::NoCurrencyWords {
+            code: self.currency().to_string(),
+            language,
+        })?;
+        let mut words = language.count(major, &names.major);
+
+        if minor_units > 0 {
+            let minor = if cheque {
+                Some(format!("{:0width$}/{}", minor, scale, width = minor_units as usize))
+            } else if minor == 0 {
+                None
+            } else {
+                Some(language.count(minor, &names.minor))
+            };
+            if let Some(minor) = minor {
+                words = format!("{} {} {}", words, language.and(), minor);
+            }
+        }
+        if units < 0 {
+            words = format!("{} {}", language.minus(), words);
+        }
+
+        let mut chars = words.chars();
+        Ok(chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default())
+    }
+}
+
+/// Splits `n` into groups of three digits, least significant first.
+fn groups_of_thousands(mut n: u64) -> Vec<u64> {
+    let mut groups = Vec::new();
+    while n > 0 {
+        groups.push(n % 1000);
+        n /= 1000;
+    }
+    groups
+}
+
+const EN_ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+const EN_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const EN_SCALES: [&str; 7] = [
+    "", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion",
+];
+
+fn english_below_1000(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, (n % 100) as usize);
+    let rest = match rest {
+        0 => String::new(),
+        1..=19 => EN_ONES[rest].to_string(),
+        _ if rest % 10 == 0 => EN_TENS[rest / 10].to_string(),
+        _ => format!("{}-{}", EN_TENS[rest / 10], EN_ONES[rest % 10]),
+    };
+    match (hundreds, rest.is_empty()) {
+        (0, _) => rest,
+        (h, true) => format!("{} hundred", EN_ONES[h as usize]),
+        (h, false) => format!("{} hundred {}", EN_ONES[h as usize], rest),
+    }
+}
+
+/// English, with the short scale: "one hundred twenty-three thousand four".
+fn english(n: u64) -> String {
+    if n == 0 {
+        return EN_ONES[0].to_string();
+    }
+    let groups = groups_of_thousands(n);
+    let mut words: Vec<String> = Vec::new();
+    for (i, &group) in groups.iter().enumerate().rev().filter(|(_, g)| **g > 0) {
+        words.push(english_below_1000(group));
+        if i > 0 {
+            words.push(EN_SCALES[i].to_string());
+        }
+    }
+    words.join(" ")
+}
+
+const FR_ONES: [&str; 17] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze",
+    "douze", "treize", "quatorze", "quinze", "seize",
+];
+const FR_TENS: [&str; 9] = [
+    "", "", "vingt", "trente", "quarante", "cinquante", "soixante", "", "quatre-vingt",
+];
+const FR_SCALES: [&str; 7] = ["", "mille", "million", "milliard", "billion", "billiard", "trillion"];
+
+/// `plural_s` is whether a final "vingt" or "cent" takes an s, as it does
+/// at the end of a number or before a noun such as "millions", but not
+/// before "mille".
+fn french_below_100(n: u64, feminine: bool, plural_s: bool) -> String {
+    if n == 1 && feminine {
+        return "une".to_string();
+    }
+    if n < 17 {
+        return FR_ONES[n as usize].to_string();
+    }
+    if n < 20 {
+        return format!("dix-{}", FR_ONES[n as usize - 10]);
+    }
+    // 70–79 and 90–99 count on from 60 and 80: "soixante-douze".
+    let (tens, rest) = match n / 10 {
+        7 => (6, n - 60),
+        9 => (8, n - 80),
+        t => (t, n % 10),
+    };
+    let word = FR_TENS[tens as usize];
+    match rest {
+        0 if tens == 8 && plural_s => "quatre-vingts".to_string(),
+        0 => word.to_string(),
+        1 | 11 if tens != 8 => format!("{} et {}", word, french_below_100(rest, feminine, plural_s)),
+        _ => format!("{}-{}", word, french_below_100(rest, feminine, plural_s)),
+    }
+}
+
+fn french_below_1000(n: u64, feminine: bool, plural_s: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let rest_words = french_below_100(rest, feminine, plural_s);
+    match (hundreds, rest) {
+        (0, _) => rest_words,
+        (1, 0) => "cent".to_string(),
+        (1, _) => format!("cent {}", rest_words),
+        (h, 0) => format!("{} cent{}", FR_ONES[h as usize], if plural_s { "s" } else { "" }),
+        (h, _) => format!("{} cent {}", FR_ONES[h as usize], rest_words),
+    }
+}
+
+/// French, with the long scale and traditional hyphenation: "deux cent
+/// mille", "quatre-vingt-un", "vingt et une".
+fn french(n: u64, feminine: bool) -> String {
+    if n == 0 {
+        return FR_ONES[0].to_string();
+    }
+    let groups = groups_of_thousands(n);
+    let mut words: Vec<String> = Vec::new();
+    for (i, &group) in groups.iter().enumerate().rev().filter(|(_, g)| **g > 0) {
+        match i {
+            0 => words.push(french_below_1000(group, feminine, true)),
+            1 if group == 1 => words.push(FR_SCALES[1].to_string()),
+            1 => words.push(format!("{} {}", french_below_1000(group, false, false), FR_SCALES[1])),
+            _ => {
+                let plural = if group > 1 { "s" } else { "" };
+                words.push(format!("{} {}{}", french_below_1000(group, false, true), FR_SCALES[i], plural));
+            }
+        }
+    }
+    words.join(" ")
+}
+
+const DE_ONES: [&str; 20] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn",
+    "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn", "achtzehn",
+    "neunzehn",
+];
+const DE_TENS: [&str; 10] = [
+    "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+const DE_SCALES: [(&str, &str); 5] = [
+    ("Million", "Millionen"),
+    ("Milliarde", "Milliarden"),
+    ("Billion", "Billionen"),
+    ("Billiarde", "Billiarden"),
+    ("Trillion", "Trillionen"),
+];
+
+/// `prefix` is whether the number is part of a compound, where 1 is
+/// "ein": "eintausend", but "hunderteins".
+fn german_below_1000(n: u64, prefix: bool) -> String {
+    let one = |d: u64| if d == 1 { "ein" } else { DE_ONES[d as usize] };
+    let (hundreds, rest) = (n / 100, n % 100);
+    let rest_words = match rest {
+        0 => String::new(),
+        1 if prefix => "ein".to_string(),
+        1..=19 => DE_ONES[rest as usize].to_string(),
+        _ if rest % 10 == 0 => DE_TENS[rest as usize / 10].to_string(),
+        _ => format!("{}und{}", one(rest % 10), DE_TENS[rest as usize / 10]),
+    };
+    match hundreds {
+        0 => rest_words,
+        h => format!("{}hundert{}", one(h), rest_words),
+    }
+}
+
+/// German: numbers below a million are one word, larger scales are
+/// separate nouns: "zwei Millionen dreihunderttausendeins".
+fn german(n: u64) -> String {
+    match n {
+        0 => return DE_ONES[0].to_string(),
+        1 => return "ein".to_string(),
+        _ => {}
+    }
+    let groups = groups_of_thousands(n);
+    let mut words: Vec<String> = Vec::new();
+    for (i, &group) in groups.iter().enumerate().rev().filter(|(_, g)| **g > 0).filter(|(i, _)| *i >= 2) {
+        let (one, other) = DE_SCALES[i - 2];
+        match group {
+            1 => words.push(format!("eine {}", one)),
+            _ => words.push(format!("{} {}", german_below_1000(group, false), other)),
+        }
+    }
+    let thousands = groups.get(1).copied().unwrap_or(0);
+    let units = groups.first().copied().unwrap_or(0);
+    let mut below_million = String::new();
+    if thousands > 0 {
+        below_million = format!("{}tausend", german_below_1000(thousands, true));
+    }
+    if units > 0 {
+        below_million.push_str(&german_below_1000(units, false));
+    }
+    if !below_million.is_empty() {
+        words.push(below_million);
+    }
+    words.join(" ")
+}
+
+const ES_BELOW_30: [&str; 30] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez",
+    "once", "doce", "trece", "catorce", "quince", "dieciséis", "diecisiete", "dieciocho",
+    "diecinueve", "veinte", "veintiuno", "veintidós", "veintitrés", "veinticuatro", "veinticinco",
+    "veintiséis", "veintisiete", "veintiocho", "veintinueve",
+];
+const ES_TENS: [&str; 10] = [
+    "", "", "", "treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa",
+];
+const ES_HUNDREDS: [&str; 10] = [
+    "", "", "doscient", "trescient", "cuatrocient", "quinient", "seiscient", "setecient",
+    "ochocient", "novecient",
+];
+const ES_SCALES: [(&str, &str); 3] = [
+    ("millón", "millones"),
+    ("billón", "billones"),
+    ("trillón", "trillones"),
+];
+
+/// Numbers before a noun, where 1 agrees with it: "un", "una",
+/// "veintiún", "veintiuna".
+fn spanish_below_1000(n: u64, feminine: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let one = if feminine { "una" } else { "un" };
+    let rest_words = match rest {
+        0 => String::new(),
+        1 => one.to_string(),
+        21 => if feminine { "veintiuna" } else { "veintiún" }.to_string(),
+        2..=29 => ES_BELOW_30[rest as usize].to_string(),
+        _ if rest % 10 == 0 => ES_TENS[rest as usize / 10].to_string(),
+        _ if rest % 10 == 1 => format!("{} y {}", ES_TENS[rest as usize / 10], one),
+        _ => format!("{} y {}", ES_TENS[rest as usize / 10], ES_BELOW_30[rest as usize % 10]),
+    };
+    let hundreds_words = match hundreds {
+        0 => return rest_words,
+        1 if rest == 0 => "cien".to_string(),
+        1 => "ciento".to_string(),
+        h => format!("{}{}", ES_HUNDREDS[h as usize], if feminine { "as" } else { "os" }),
+    };
+    if rest == 0 { hundreds_words } else { format!("{} {}", hundreds_words, rest_words) }
+}
+
+fn spanish_below_million(n: u64, feminine: bool) -> String {
+    let (thousands, units) = (n / 1000, n % 1000);
+    let mut words: Vec<String> = Vec::new();
+    match thousands {
+        0 => {}
+        1 => words.push("mil".to_string()),
+        t => words.push(format!("{} mil", spanish_below_1000(t, feminine))),
+    }
+    if units > 0 {
+        words.push(spanish_below_1000(units, feminine));
+    }
+    words.join(" ")
+}
+
+/// Spanish, with the long scale counted in millions: "mil millones",
+/// "doscientas mil libras".
+fn spanish(n: u64, feminine: bool) -> String {
+    if n == 0 {
+        return ES_BELOW_30[0].to_string();
+    }
+    let mut words: Vec<String> = Vec::new();
+    for i in (1..=3).rev() {
+        let group = n / 1_000_000u64.pow(i) % 1_000_000;
+        let (one, other) = ES_SCALES[i as usize - 1];
+        match group {
+            0 => {}
+            1 => words.push(format!("un {}", one)),
+            g => words.push(format!("{} {}", spanish_below_million(g, false), other)),
+        }
+    }
+    if !n.is_multiple_of(1_000_000) {
+        words.push(spanish_below_million(n % 1_000_000, feminine));
+    }
+    words.join(" ")
+}
+
+#[test]
+fn currency_amount_to_words() {
+    let words = |code: &str, s: &str| amount(code, s).to_words(Language::English).unwrap();
+    let cheque = |code: &str, s: &str| amount(code, s).to_cheque_words(Language::English).unwrap();
+
+    assert_eq!(cheque("USD", "123.45"), "One hundred twenty-three dollars and 45/100");
+    assert_eq!(words("USD", "123.45"), "One hundred twenty-three dollars and forty-five cents");
+    assert_eq!(cheque("USD", "1000"), "One thousand dollars and 00/100");
+    assert_eq!(words("USD", "1000"), "One thousand dollars");
+    assert_eq!(words("USD", "1.01"), "One dollar and one cent");
+    assert_eq!(words("USD", "0.5"), "Zero dollars and fifty cents");
+    assert_eq!(words("GBP", "-2.01"), "Minus two pounds and one penny");
+    assert_eq!(words("EUR", "1000000.10"), "One million euros and ten cents");
+    assert_eq!(
+        words("USD", "92233720368547758.07"),
+        "Ninety-two quadrillion two hundred thirty-three trillion seven hundred twenty billion \
+         three hundred sixty-eight million five hundred forty-seven thousand seven hundred \
+         fifty-eight dollars and seven cents"
+    );
+
+    // Zero-decimal and three-decimal currencies
+    assert_eq!(cheque("JPY", "1234"), "One thousand two hundred thirty-four yen");
+    assert_eq!(words("JPY", "1"), "One yen");
+    assert_eq!(cheque("BHD", "12.500"), "Twelve dinars and 500/1000");
+    assert_eq!(words("BHD", "12.500"), "Twelve dinars and five hundred fils");
+    assert_eq!(cheque("KWD", "1.005"), "One dinar and 005/1000");
+    assert_eq!(words("KWD", "1.001"), "One dinar and one fils");
+
+    // Currencies without unit names in the language are not guessed at
+    let no_words = |code: &str, language| Err(Error::NoCurrencyWords { code: code.into(), language });
+    assert_eq!(amount("OMR", "12.5").to_cheque_words(Language::English), no_words("OMR", Language::English));
+    assert_eq!(amount("OMR", "12").to_words(Language::French), no_words("OMR", Language::French));
+
+    assert_eq!(
+        amount("USD", "1.005").to_words(Language::English),
+        Err(Error::ExcessPrecision { amount: "USD 1.005".into(), minor_units: 2 })
+    );
+    assert_eq!(amount("XAU", "1").to_words(Language::English), Err(Error::NoMinorUnit("XAU".into())));
+}
+
+#[test]
+fn currency_amount_to_words_languages() {
+    let words = |language, code: &str, s: &str| amount(code, s).to_words(language).unwrap();
+    let (fr, de, es) = (Language::French, Language::German, Language::Spanish);
+
+    assert_eq!(words(fr, "CAD", "123.45"), "Cent vingt-trois dollars et quarante-cinq cents");
+    assert_eq!(words(fr, "CAD", "1.80"), "Un dollar et quatre-vingts cents");
+    assert_eq!(words(fr, "EUR", "0.81"), "Zéro euro et quatre-vingt-un centimes");
+    assert_eq!(words(fr, "GBP", "21"), "Vingt et une livres");
+    assert_eq!(words(fr, "GBP", "1"), "Une livre");
+    assert_eq!(words(fr, "EUR", "71"), "Soixante et onze euros");
+    assert_eq!(words(fr, "EUR", "99"), "Quatre-vingt-dix-neuf euros");
+    assert_eq!(words(fr, "EUR", "200"), "Deux cents euros");
+    assert_eq!(words(fr, "EUR", "280000"), "Deux cent quatre-vingt mille euros");
+    assert_eq!(words(fr, "EUR", "1001"), "Mille un euros");
+    assert_eq!(words(fr, "EUR", "2000000"), "Deux millions d'euros");
+    assert_eq!(words(fr, "USD", "300000000"), "Trois cents millions de dollars");
+    assert_eq!(words(fr, "USD", "1000000001"), "Un milliard un dollars");
+
+    assert_eq!(words(de, "EUR", "123.45"), "Einhundertdreiundzwanzig Euro und fünfundvierzig Cent");
+    assert_eq!(words(de, "EUR", "1.01"), "Ein Euro und ein Cent");
+    assert_eq!(words(de, "CHF", "101.10"), "Einhunderteins Franken und zehn Rappen");
+    assert_eq!(words(de, "EUR", "21000"), "Einundzwanzigtausend Euro");
+    assert_eq!(words(de, "EUR", "1001000"), "Eine Million eintausend Euro");
+    assert_eq!(words(de, "USD", "2300000000"), "Zwei Milliarden dreihundert Millionen Dollar");
+    assert_eq!(words(de, "JPY", "0"), "Null Yen");
+
+    assert_eq!(words(es, "MXN", "123.45"), "Ciento veintitrés pesos con cuarenta y cinco centavos");
+    assert_eq!(words(es, "USD", "1.01"), "Un dólar con un centavo");
+    assert_eq!(words(es, "USD", "21"), "Veintiún dólares");
+    assert_eq!(words(es, "GBP", "21"), "Veintiuna libras");
+    assert_eq!(words(es, "GBP", "231000"), "Doscientas treinta y una mil libras");
+    assert_eq!(words(es, "EUR", "100"), "Cien euros");
+    assert_eq!(words(es, "EUR", "1000000"), "Un millón de euros");
+    assert_eq!(words(es, "EUR", "1000000000"), "Mil millones de euros");
+    assert_eq!(words(es, "EUR", "-515.50"), "Menos quinientos quince euros con cincuenta céntimos");
+
+    assert_eq!(words(fr, "BHD", "12.500"), "Douze dinars et cinq cents fils");
+    assert_eq!(words(de, "KWD", "1.001"), "Ein Dinar und ein Fils");
+    assert_eq!(words(es, "BHD", "21.021"), "Veintiún dinares con veintiún fils");
+}
@@ -0,0 +1,319 @@
+use dcbor::prelude::*;
+use num_bigint_dig::BigInt;
+use num_traits::{ Signed, ToPrimitive, Zero };
+
+use crate::{
+    DecimalFraction,
+    Error,
+    Result,
+    bignum::{ big_pow10, bignum_from_cbor, bignum_to_cbor, strip_trailing_zeros },
+    decimal_fraction::parse_decimal_parts,
+    tags::TAG_DECIMAL_FRACTION,
+};
+
+/// The most zeros that parsing will append to the mantissa to absorb an
+/// exponent above `i8::MAX`, so that a short string like `"1e30000000"`
+/// can't demand a mantissa of millions of digits.
+const MAX_EXPONENT_SHIFT: u32 = 4096;
+
+/// A decimal fraction with an arbitrary-precision mantissa.
+///
+/// RFC 8949 §3.4.4 allows the mantissa of a tag 4 decimal fraction to be a
+/// bignum (tag 2 or 3). This type encodes to the same `4([exponent,
+/// mantissa])` structure as [`DecimalFraction`], using a plain integer for
+/// the mantissa when it fits in 64 bits and a bignum otherwise, so values that
+/// fit in a `DecimalFraction` encode identically with either type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigDecimalFraction {
+    pub exponent: i8,
+    pub mantissa: BigInt,
+}
+
+impl BigDecimalFraction {
+    /// Create a new `BigDecimalFraction` from raw parts.
+    pub fn new(exponent: i8, mantissa: impl Into<BigInt>) -> Self {
+        Self { exponent, mantissa: mantissa.into() }
+    }
+
+    /// Returns the canonical form of this value: trailing zeros of the
+    /// mantissa are moved into the exponent, and zero is always `0e0`.
+    pub fn normalize(&self) -> Self {
+        if self.mantissa.is_zero() {
+            return Self::new(0, 0);
+        }
+        let limit = (i8::MAX as i16 - self.exponent as i16) as u32;
+        let (mantissa, shift) = strip_trailing_zeros(&self.mantissa, limit);
+        Self::new(self.exponent + shift as i8, mantissa)
+    }
+
+    /// Returns `true` if this value is already in canonical form.
+    pub fn is_normalized(&self) -> bool {
+        *self == self.normalize()
+    }
+
+    /// Rewrites `self` and `other` with a common exponent, the smaller of the
+    /// two. This never overflows.
+    fn aligned(&self, other: &Self) -> (i8, BigInt, BigInt) {
+        let exponent = self.exponent.min(other.exponent);
+        let a = &self.mantissa * big_pow10((self.exponent as i16 - exponent as i16) as u32);
+        let b = &other.mantissa * big_pow10((other.exponent as i16 - exponent as i16) as u32);
+        (exponent, a, b)
+    }
+
+    /// Exact multiplication. Only the exponent can overflow.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self> {
+        let exponent = self.exponent
+            .checked_add(other.exponent)
+            .ok_or(Error::ExponentOverflow)?;
+        Ok(Self::new(exponent, &self.mantissa * &other.mantissa))
+    }
+}
+
+impl std::ops::Add for BigDecimalFraction {
+    type Output = Self;
+
+    /// Exact addition. The result carries the smaller of the two exponents.
+    fn add(self, rhs: Self) -> Self {
+        let (exponent, a, b) = self.aligned(&rhs);
+        Self::new(exponent, a + b)
+    }
+}
+
+impl std::ops::Sub for BigDecimalFraction {
+    type Output = Self;
+
+    /// Exact subtraction. The result carries the smaller of the two exponents.
+    fn sub(self, rhs: Self) -> Self {
+        let (exponent, a, b) = self.aligned(&rhs);
+        Self::new(exponent, a - b)
+    }
+}
+
+impl std::ops::Mul for BigDecimalFraction {
+    type Output = Result<Self>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(&rhs)
+    }
+}
+
+impl std::ops::Neg for BigDecimalFraction {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(self.exponent, -self.mantissa)
+    }
+}
+
+impl From<DecimalFraction> for BigDecimalFraction {
+    fn from(value: DecimalFraction) -> Self {
+        Self::new(value.exponent, value.mantissa)
+    }
+}
+
+impl TryFrom<BigDecimalFraction> for DecimalFraction {
+    type Error = Error;
+
+    /// Fails if the mantissa doesn't fit in an `i64`. The exponent is kept
+    /// as is; call [`BigDecimalFraction::normalize`] first to shed trailing
+    /// zeros.
+    fn try_from(value: BigDecimalFraction) -> Result<Self> {
+        let mantissa = value.mantissa.to_i64().ok_or(Error::MantissaOverflow)?;
+        Ok(DecimalFraction::new(value.exponent, mantissa))
+    }
+}
+
+impl std::fmt::Display for BigDecimalFraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.mantissa.is_zero() {
+            return write!(f, "0");
+        }
+
+        let prefix = if self.mantissa.is_negative() { "-" } else { "" };
+        let digits = self.mantissa.abs().to_string();
+
+        if self.exponent >= 0 {
+            write!(f, "{}{}{}", prefix, digits, "0".repeat(self.exponent as usize))
+        } else {
+            let abs_exp = self.exponent.unsigned_abs() as usize;
+            if digits.len() <= abs_exp {
+                let padding = abs_exp - digits.len();
+                write!(f, "{}0.{}{}", prefix, "0".repeat(padding), digits)
+            } else {
+                let (integer_part, fractional_part) = digits.split_at(digits.len() - abs_exp);
+                write!(f, "{}{}.{}", prefix, integer_part, fractional_part)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for BigDecimalFraction {
+    type Err = Error;
+
+    /// Parses the same notation as [`DecimalFraction`]'s `FromStr`, with no
+    /// limit on the number of digits.
+    fn from_str(s: &str) -> Result<Self> {
+        let (negative, mut digits, mut exponent) = parse_decimal_parts(s)?;
+        if digits.is_empty() {
+            return Ok(Self::new(exponent.clamp(i8::MIN as i64, i8::MAX as i64) as i8, 0));
+        }
+
+        // An exponent that is too small can be balanced by trailing zeros.
+        while exponent < i8::MIN as i64 && digits.ends_with('0') {
+            digits.pop();
+            exponent += 1;
+        }
+
+        let magnitude = BigInt::parse_bytes(digits.as_bytes(), 10)
+            .expect("digits were validated by parse_decimal_parts");
+        let mut mantissa = if negative { -magnitude } else { magnitude };
+
+        // An exponent that is too large can be absorbed into the mantissa,
+        // within reason.
+        if exponent > i8::MAX as i64 {
+            let shift = u32::try_from(exponent - i8::MAX as i64)
+                .ok()
+                .filter(|shift| *shift <= MAX_EXPONENT_SHIFT)
+                .ok_or(Error::ExponentOverflow)?;
+            mantissa *= big_pow10(shift);
+            exponent = i8::MAX as i64;
+        }
+        let exponent = i8::try_from(exponent).map_err(|_| Error::ExponentOverflow)?;
+
+        Ok(Self::new(exponent, mantissa))
+    }
+}
+
+impl BigDecimalFraction {
+    /// Encodes the canonical form returned by
+    /// [`BigDecimalFraction::normalize`], as
+    /// [`DecimalFraction::to_canonical_cbor`] does.
+    pub fn to_canonical_cbor(&self) -> CBOR {
+        self.normalize().to_cbor()
+    }
+
+    /// Decodes like `TryFrom<CBOR>`, but rejects values that are not in
+    /// canonical form, including bignum mantissas that are not in preferred
+    /// serialization.
+    pub fn try_from_canonical(cbor: CBOR) -> Result<Self, dcbor::Error> {
+        let value = Self::decode(cbor, true)?;
+        if !value.is_normalized() {
+            return Err(Error::NonCanonicalDecimalFraction(value.to_string()).into());
+        }
+        Ok(value)
+    }
+
+    fn decode(cbor: CBOR, strict: bool) -> Result<Self, dcbor::Error> {
+        let item = cbor.try_into_expected_tagged_value(TAG_DECIMAL_FRACTION)?;
+        let arr = item.try_into_array()?;
+
+        if arr.len() != 2 {
+            return Err("Expected a two-element array".into());
+        }
+
+        let exponent: i8 = arr[0].clone().try_into()?;
+        let mantissa = bignum_from_cbor(arr[1].clone(), strict)?;
+        Ok(BigDecimalFraction::new(exponent, mantissa))
+    }
+}
+
+impl From<BigDecimalFraction> for CBOR {
+    fn from(value: BigDecimalFraction) -> Self {
+        let v = vec![CBOR::from(value.exponent), bignum_to_cbor(&value.mantissa)].to_cbor();
+        CBOR::to_tagged_value(TAG_DECIMAL_FRACTION, v)
+    }
+}
+
+impl TryFrom<CBOR> for BigDecimalFraction {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self, Self::Error> {
+        Self::decode(cbor, false)
+    }
+}
+
+#[test]
+fn big_decimal_fraction_cbor() {
+    // 1234.567890123456789012 ETH in wei doesn't fit in an i64
+    let wei: BigDecimalFraction = "1234.567890123456789012".parse().unwrap();
+    assert_eq!(wei.exponent, -18);
+    assert_eq!(wei.to_string(), "1234.567890123456789012");
+
+    let cbor = wei.to_cbor();
+    assert_eq!(cbor.diagnostic_flat(), "4([-18, 2(h'42ed123b0bd8203a14')])");
+    let decoded: BigDecimalFraction = cbor.clone().try_into().unwrap();
+    assert_eq!(decoded, wei);
+
+    // DecimalFraction can't decode a bignum mantissa
+    assert!(DecimalFraction::try_from(cbor).is_err());
+    assert_eq!(DecimalFraction::try_from(wei.clone()), Err(Error::MantissaOverflow));
+
+    // Negative bignum mantissas use tag 3
+    let negative = -wei;
+    assert_eq!(negative.to_cbor().diagnostic_flat(), "4([-18, 3(h'42ed123b0bd8203a13')])");
+    let decoded: BigDecimalFraction = negative.to_cbor().try_into().unwrap();
+    assert_eq!(decoded, negative);
+
+    // Canonical encoding strips trailing zeros, and canonical decoding
+    // rejects padded bignums
+    let padded = BigDecimalFraction::new(-20, big_pow10(22));
+    assert_eq!(padded.to_canonical_cbor().diagnostic_flat(), "4([2, 1])");
+    assert!(BigDecimalFraction::try_from_canonical(padded.to_cbor()).is_err());
+    let bignum_one = CBOR::to_tagged_value(
+        TAG_DECIMAL_FRACTION,
+        vec![CBOR::from(0), CBOR::to_tagged_value(crate::TAG_POSITIVE_BIGNUM, CBOR::to_byte_string([0u8, 1]))],
+    );
+    assert_eq!(BigDecimalFraction::try_from(bignum_one.clone()).unwrap(), BigDecimalFraction::new(0, BigInt::from(1)));
+    assert!(BigDecimalFraction::try_from_canonical(bignum_one).is_err());
+    assert_eq!(BigDecimalFraction::try_from_canonical(negative.to_cbor()).unwrap(), negative);
+}
+
+#[test]
+fn big_decimal_fraction_from_str() {
+    let parse = |s: &str| s.parse::<BigDecimalFraction>();
+
+    assert_eq!(parse("-12.50").unwrap(), BigDecimalFraction::new(-2, -1250));
+    let large = parse("1e1000").unwrap();
+    assert_eq!(large.exponent, i8::MAX);
+    assert_eq!(large.normalize(), BigDecimalFraction::new(i8::MAX, big_pow10(873)));
+
+    // Too-small exponents are balanced by trailing zeros, as for DecimalFraction
+    assert_eq!(parse("100e-130").unwrap(), BigDecimalFraction::new(-128, 1));
+    assert_eq!(parse("-1.200e-126").unwrap(), BigDecimalFraction::new(-128, -120));
+    assert_eq!(parse("1e-129"), Err(Error::ExponentOverflow));
+
+    // Huge exponents fail at once rather than building a huge mantissa
+    let start = std::time::Instant::now();
+    for s in ["1e30000000", "1e4294967000", "1e9223372036854775807"] {
+        assert_eq!(parse(s), Err(Error::ExponentOverflow), "{s}");
+    }
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+}
+
+#[test]
+fn big_decimal_fraction_interop() {
+    // Values that fit encode exactly like DecimalFraction
+    let small = DecimalFraction::new(-1, 11);
+    let big = BigDecimalFraction::from(small);
+    assert_eq!(big.to_cbor_data(), small.to_cbor_data());
+    let decoded: BigDecimalFraction = small.to_cbor().try_into().unwrap();
+    assert_eq!(decoded, big);
+    assert_eq!(DecimalFraction::try_from(big).unwrap(), small);
+
+    // Arithmetic never overflows the mantissa
+    let max = BigDecimalFraction::from(DecimalFraction::new(0, i64::MAX));
+    let sum = max.clone() + max.clone();
+    assert_eq!(sum.to_string(), "18446744073709551614");
+    let tiny = BigDecimalFraction::new(-30, 1);
+    assert_eq!((max.clone() - tiny).to_string(), "9223372036854775806.999999999999999999999999999999");
+    let product = max.clone().checked_mul(&max).unwrap();
+    assert_eq!(product.to_string(), "85070591730234615847396907784232501249");
+    assert_eq!(
+        BigDecimalFraction::new(100, 1).checked_mul(&BigDecimalFraction::new(100, 1)),
+        Err(Error::ExponentOverflow)
+    );
+
+    let padded = BigDecimalFraction::new(-20, big_pow10(22));
+    assert_eq!(padded.normalize(), BigDecimalFraction::new(2, 1));
+    assert!(!padded.is_normalized());
+}
@@ -0,0 +1,226 @@
+use dcbor::prelude::*;
+use num_bigint_dig::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::{
+    DecimalFraction,
+    Error,
+    Result,
+    bignum::{ bignum_from_cbor, strip_trailing_zeros },
+    tags::TAG_BIGFLOAT,
+};
+
+/// A binary floating-point value `mantissa * 2^exponent`, encoded as the
+/// RFC 8949 §3.4.4 bigfloat: `5([exponent, mantissa])`.
+///
+/// The exponent is an `i16` so that every finite `f64`, including
+/// subnormals, converts to a `BigFloat` exactly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BigFloat {
+    pub exponent: i16,
+    pub mantissa: i64,
+}
+
+impl BigFloat {
+    /// Create a new `BigFloat` from raw parts.
+    pub fn new(exponent: i16, mantissa: i64) -> Self {
+        Self { exponent, mantissa }
+    }
+
+    /// Returns the canonical form of this value: trailing zero bits of the
+    /// mantissa are moved into the exponent, and zero is always `0 * 2^0`.
+    pub fn normalize(self) -> Self {
+        if self.mantissa == 0 {
+            return Self::new(0, 0);
+        }
+        let headroom = (i16::MAX as i32 - self.exponent as i32) as u32;
+        let shift = self.mantissa.trailing_zeros().min(headroom);
+        Self::new(self.exponent + shift as i16, self.mantissa >> shift)
+    }
+
+    /// Converts an `f64` to the `BigFloat` exactly equal to it. Fails only
+    /// on NaN and infinities.
+    pub fn from_f64(value: f64) -> Result<Self> {
+        if !value.is_finite() {
+            return Err(Error::NonFiniteFloat(value));
+        }
+
+        let bits = value.to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i16;
+        let fraction = (bits & ((1u64 << 52) - 1)) as i64;
+        let (significand, exponent) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1i64 << 52), biased - 1075)
+        };
+        let mantissa = if value.is_sign_negative() { -significand } else { significand };
+
+        Ok(Self::new(exponent, mantissa).normalize())
+    }
+
+    /// Converts to `f64`, failing if the value can't be represented exactly
+    /// (too many significant bits, or out of range).
+    pub fn to_f64(self) -> Result<f64> {
+        let inexact = || Error::InexactFloat(format!("{}", self));
+        let value = self.normalize();
+        if value.mantissa == 0 {
+            return Ok(0.0);
+        }
+
+        // With an odd mantissa, the value needs the lowest set bit at
+        // 2^exponent and at most 53 significant bits.
+        if value.mantissa.unsigned_abs() > 1u64 << 53 || !(-1074..=1023).contains(&value.exponent) {
+            return Err(inexact());
+        }
+        let power = if value.exponent >= -1022 {
+            f64::from_bits(((value.exponent + 1023) as u64) << 52)
+        } else {
+            f64::from_bits(1u64 << (value.exponent + 1074))
+        };
+        let result = value.mantissa as f64 * power;
+
+        // Rules out overflow and subnormal rounding.
+        if !result.is_finite() || Self::from_f64(result)? != value {
+            return Err(inexact());
+        }
+        Ok(result)
+    }
+}
+
+impl TryFrom<BigFloat> for f64 {
+    type Error = Error;
+
+    fn try_from(value: BigFloat) -> Result<Self> {
+        value.to_f64()
+    }
+}
+
+impl TryFrom<BigFloat> for DecimalFraction {
+    type Error = Error;
+
+    /// Every bigfloat is a terminating decimal, since `2^-k = 5^k / 10^k`,
+    /// but the result often needs more digits than an `i64` mantissa holds,
+    /// in which case this fails. Integers too large for the mantissa keep
+    /// their trailing decimal zeros in the exponent instead: `2^22 * 5^22` is
+    /// `4([22, 1])`.
+    fn try_from(value: BigFloat) -> Result<Self> {
+        let value = value.normalize();
+        if value.exponent >= 0 {
+            // An integer: mantissa * 2^exponent
+            let magnitude = BigInt::from(value.mantissa) << value.exponent as usize;
+            if let Some(mantissa) = magnitude.to_i64() {
+                return Ok(DecimalFraction::new(0, mantissa));
+            }
+            let (mantissa, zeros) = strip_trailing_zeros(&magnitude, i8::MAX as u32);
+            let mantissa = mantissa.to_i64().ok_or(Error::MantissaOverflow)?;
+            return Ok(DecimalFraction::new(zeros as i8, mantissa));
+        }
+
+        // mantissa / 2^k = mantissa * 5^k / 10^k
+        let magnitude = 5i128
+            .checked_pow(value.exponent.unsigned_abs() as u32)
+            .and_then(|p| (value.mantissa as i128).checked_mul(p))
+            .ok_or(Error::MantissaOverflow)?;
+        let exponent = i8::try_from(value.exponent).map_err(|_| Error::ExponentOverflow)?;
+        let mantissa = i64::try_from(magnitude).map_err(|_| Error::MantissaOverflow)?;
+
+        Ok(DecimalFraction::new(exponent, mantissa))
+    }
+}
+
+impl std::fmt::Display for BigFloat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}p{}", self.mantissa, self.exponent)
+    }
+}
+
+impl From<BigFloat> for CBOR {
+    fn from(value: BigFloat) -> Self {
+        let v = vec![value.exponent as i64, value.mantissa].to_cbor();
+        CBOR::to_tagged_value(TAG_BIGFLOAT, v)
+    }
+}
+
+impl TryFrom<CBOR> for BigFloat {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self, Self::Error> {
+        let item = cbor.try_into_expected_tagged_value(TAG_BIGFLOAT)?;
+        let arr = item.try_into_array()?;
+
+        if arr.len() != 2 {
+            return Err("Expected a two-element array".into());
+        }
+
+        // The mantissa may arrive as a bignum, but must fit in an i64.
+        let exponent: i16 = arr[0].clone().try_into()?;
+        let mantissa = bignum_from_cbor(arr[1].clone(), false)?
+            .to_i64()
+            .ok_or(Error::MantissaOverflow)?;
+
+        Ok(BigFloat::new(exponent, mantissa))
+    }
+}
+
+#[test]
+fn big_float_f64() {
+    assert_eq!(BigFloat::from_f64(1.5).unwrap(), BigFloat::new(-1, 3));
+    assert_eq!(BigFloat::from_f64(-0.0).unwrap(), BigFloat::new(0, 0));
+    assert_eq!(BigFloat::from_f64(1024.0).unwrap(), BigFloat::new(10, 1));
+    assert_eq!(BigFloat::from_f64(5e-324).unwrap(), BigFloat::new(-1074, 1));
+
+    for x in [0.0, 1.5, -0.1, 1.0 / 3.0, 6.02214076e23, f64::MAX, f64::MIN_POSITIVE, -5e-324] {
+        let b = BigFloat::from_f64(x).unwrap();
+        assert_eq!(b.to_f64().unwrap().to_bits(), x.to_bits(), "{x}");
+    }
+
+    // Unnormalized inputs convert too
+    assert_eq!(BigFloat::new(-3, 12).to_f64().unwrap(), 1.5);
+
+    // Too many significant bits, too large, or too small
+    assert!(matches!(BigFloat::new(0, (1 << 53) + 1).to_f64(), Err(Error::InexactFloat(_))));
+    assert!(matches!(BigFloat::new(1024, 1).to_f64(), Err(Error::InexactFloat(_))));
+    assert!(matches!(BigFloat::new(-1075, 1).to_f64(), Err(Error::InexactFloat(_))));
+    assert_eq!(BigFloat::new(-1074, 3).to_f64().unwrap(), 1.5e-323);
+    assert!(matches!(BigFloat::new(-1080, 3).to_f64(), Err(Error::InexactFloat(_))));
+    assert!(matches!(BigFloat::from_f64(f64::NAN), Err(Error::NonFiniteFloat(_))));
+}
+
+#[test]
+fn big_float_decimal_fraction() {
+    let to_decimal = |b: BigFloat| DecimalFraction::try_from(b).unwrap();
+    assert_eq!(to_decimal(BigFloat::new(-1, 3)), DecimalFraction::new(-1, 15));
+    assert_eq!(to_decimal(BigFloat::new(-2, -5)), DecimalFraction::new(-2, -125));
+    assert_eq!(to_decimal(BigFloat::new(4, 3)), DecimalFraction::new(0, 48));
+    assert_eq!(to_decimal(BigFloat::new(-8, 256)), DecimalFraction::new(0, 1));
+
+    // 0.1 as an f64 has no short decimal expansion
+    let tenth = BigFloat::from_f64(0.1).unwrap();
+    assert_eq!(DecimalFraction::try_from(tenth), Err(Error::MantissaOverflow));
+    assert_eq!(DecimalFraction::try_from(BigFloat::new(100, 1)), Err(Error::MantissaOverflow));
+
+    // Integers beyond the mantissa's range keep their trailing zeros in the
+    // exponent
+    assert_eq!(to_decimal(BigFloat::new(22, 5i64.pow(22))), DecimalFraction::new(22, 1));
+    assert_eq!(to_decimal(BigFloat::new(70, -(5i64.pow(27)))), DecimalFraction::new(27, -(1 << 43)));
+    assert_eq!(DecimalFraction::try_from(BigFloat::new(64, 3)), Err(Error::MantissaOverflow));
+}
+
+#[test]
+fn big_float_cbor() {
+    let value = BigFloat::new(-1, 3);
+    let cbor = value.to_cbor();
+    assert_eq!(cbor.diagnostic_flat(), "5([-1, 3])");
+    let decoded: BigFloat = cbor.try_into().unwrap();
+    assert_eq!(decoded, value);
+
+    // Bignum mantissas are accepted when they fit
+    let bignum = CBOR::to_tagged_value(
+        TAG_BIGFLOAT,
+        vec![CBOR::from(-2), CBOR::to_tagged_value(2, CBOR::to_byte_string([0x05]))],
+    );
+    assert_eq!(BigFloat::try_from(bignum).unwrap(), BigFloat::new(-2, 5));
+
+    let wrong_tag = DecimalFraction::new(-1, 3).to_cbor();
+    assert!(BigFloat::try_from(wrong_tag).is_err());
+}
@@ -0,0 +1,96 @@
+use dcbor::prelude::*;
+use num_bigint_dig::{ BigInt, Sign };
+use num_traits::{ ToPrimitive, Zero };
+
+use crate::{ TAG_NEGATIVE_BIGNUM, TAG_POSITIVE_BIGNUM };
+
+/// Encodes an integer using CBOR's preferred serialization (RFC 8949
+/// §3.4.3): a plain integer (major type 0 or 1) when it fits in 64 bits,
+/// otherwise a tag 2 or tag 3 bignum whose byte string has no leading zeros.
+pub(crate) fn bignum_to_cbor(value: &BigInt) -> CBOR {
+    if value.sign() == Sign::Minus {
+        // Tag 3 and major type 1 both carry `-1 - value`.
+        let n = -(value + 1u32);
+        match n.to_u64() {
+            Some(n) => CBORCase::Negative(n).into(),
+            None => CBOR::to_tagged_value(TAG_NEGATIVE_BIGNUM, CBOR::to_byte_string(n.to_bytes_be().1)),
+        }
+    } else {
+        match value.to_u64() {
+            Some(n) => CBORCase::Unsigned(n).into(),
+            None => CBOR::to_tagged_value(TAG_POSITIVE_BIGNUM, CBOR::to_byte_string(value.to_bytes_be().1)),
+        }
+    }
+}
+
+/// Decodes an integer that may be a plain CBOR integer or a tag 2/3
+/// bignum. If `strict` is set, bignums that are not in preferred
+/// serialization (leading zero bytes, or values that fit in a plain integer)
+/// are rejected.
+pub(crate) fn bignum_from_cbor(cbor: CBOR, strict: bool) -> dcbor::Result<BigInt> {
+    match cbor.into_case() {
+        CBORCase::Unsigned(n) => Ok(BigInt::from(n)),
+        CBORCase::Negative(n) => Ok(-BigInt::from(n) - 1u32),
+        CBORCase::Tagged(tag, item) => {
+            let negative = match tag.value() {
+                TAG_POSITIVE_BIGNUM => false,
+                TAG_NEGATIVE_BIGNUM => true,
+                _ => return Err(dcbor::Error::WrongType),
+            };
+            let bytes = item.try_into_byte_string()?;
+            if strict && (bytes.len() <= 8 || bytes[0] == 0) {
+                return Err("Bignum is not in preferred serialization".into());
+            }
+            let n = BigInt::from_bytes_be(Sign::Plus, &bytes);
+            Ok(if negative { -n - 1u32 } else { n })
+        }
+        _ => Err(dcbor::Error::WrongType),
+    }
+}
+
+/// Returns `10^n` as a `BigInt`.
+pub(crate) fn big_pow10(n: u32) -> BigInt {
+    num_traits::pow(BigInt::from(10u32), n as usize)
+}
+
+/// Divides `value` by ten as many times as it leaves no remainder, up to
+/// `limit` times, returning the quotient and how many times it divided.
+pub(crate) fn strip_trailing_zeros(value: &BigInt, limit: u32) -> (BigInt, u32) {
+    let ten = BigInt::from(10u32);
+    let mut value = value.clone();
+    let mut count = 0;
+    if value.is_zero() {
+        return (value, 0);
+    }
+    while count < limit && (&value % &ten).is_zero() {
+        value /= &ten;
+        count += 1;
+    }
+    (value, count)
+}
+
+#[test]
+fn bignum_cbor() {
+    let cases: [(&str, &str); 8] = [
+        ("0", "0"),
+        ("-1", "-1"),
+        ("18446744073709551615", "18446744073709551615"),
+        ("-18446744073709551616", "-18446744073709551616"),
+        ("18446744073709551616", "2(h'010000000000000000')"),
+        ("-18446744073709551617", "3(h'010000000000000000')"),
+        ("1000000000000000000000000", "2(h'd3c21bcecceda1000000')"),
+        ("-1000000000000000000000000", "3(h'd3c21bcecceda0ffffff')"),
+    ];
+    for (value, diagnostic) in cases {
+        let n = BigInt::parse_bytes(value.as_bytes(), 10).unwrap();
+        let cbor = bignum_to_cbor(&n);
+        assert_eq!(cbor.diagnostic_flat(), diagnostic);
+        assert_eq!(bignum_from_cbor(cbor, true).unwrap(), n);
+    }
+
+    // Non-preferred serializations are only accepted when not strict
+    let padded = CBOR::to_tagged_value(TAG_POSITIVE_BIGNUM, CBOR::to_byte_string([0u8, 1]));
+    assert_eq!(bignum_from_cbor(padded.clone(), false).unwrap(), BigInt::from(1));
+    assert!(bignum_from_cbor(padded, true).is_err());
+    assert!(bignum_from_cbor(CBOR::from("1"), false).is_err());
+}
@@ -0,0 +1,84 @@
+use crate::{ CurrencyAmount, DecimalFraction, Error, Result, RoundingMode };
+#[cfg(test)]
+use crate::currency_amount::amount;
+
+/// Currencies whose cash is rounded more coarsely than their minor unit,
+/// because the smallest coins are no longer issued, as `(code, exponent,
+/// mantissa)` of the smallest cash amount. Sorted by code.
+static CASH_INCREMENTS: &[(&str, i8, i64)] = &[
+    ("AUD", -2, 5),
+    ("CAD", -2, 5),
+    ("CHF", -2, 5),
+    ("CZK", 0, 1),
+    ("DKK", -2, 50),
+    ("HUF", 0, 5),
+    ("NOK", 0, 1),
+    ("NZD", -2, 10),
+    ("SEK", 0, 1),
+];
+
+/// The cash increment for an ISO 4217 code, if it has one.
+pub(crate) fn cash_increment(code: &str) -> Option<DecimalFraction> {
+    CASH_INCREMENTS
+        .binary_search_by(|(c, _, _)| c.cmp(&code))
+        .ok()
+        .map(|i| DecimalFraction::new(CASH_INCREMENTS[i].1, CASH_INCREMENTS[i].2))
+}
+
+impl CurrencyAmount {
+    /// Rounds this amount with `mode` to a multiple of the currency's
+    /// [cash increment](crate::CurrencyDefinition::cash_increment), or to
+    /// its minor unit if cash is paid to the minor unit: CHF 12.33 is
+    /// CHF 12.35 in cash, and SEK 12.33 is SEK 12.00.
+    ///
+    /// The result always has the currency's minor-unit exponent, like an
+    /// amount from [`CurrencyAmount::from_minor_units`], so a cash total
+    /// and the electronic total it was rounded from encode the same way.
+    /// Fails with [`Error::NoMinorUnit`] for currencies without one.
+    pub fn round_cash(&self, mode: RoundingMode) -> Result<Self> {
+        let definition = self.currency().definition();
+        let minor_units = definition
+            .as_ref()
+            .and_then(|d| d.minor_units())
+            .ok_or_else(|| Error::NoMinorUnit(self.currency().to_string()))?;
+        let exponent = -i8::try_from(minor_units).map_err(|_| Error::ExponentOverflow)?;
+
+        let rounded = match definition.and_then(|d| d.cash_increment()) {
+            None => self.amount().quantize(exponent, mode)?,
+            Some(increment) => {
+                let count = self.amount().div_with(increment, 0, mode)?;
+                count.checked_mul(increment)?.quantize(exponent, mode)?
+            }
+        };
+        Ok(Self::new(self.currency().clone(), rounded))
+    }
+}
+
+#[test]
+fn currency_amount_round_cash() {
+    use dcbor::prelude::*;
+
+    let cash = |code: &str, s: &str, mode| amount(code, s).round_cash(mode).unwrap().to_string();
+
+    assert_eq!(cash("CHF", "12.33", RoundingMode::HalfEven), "CHF 12.35");
+    assert_eq!(cash("CHF", "12.32", RoundingMode::HalfEven), "CHF 12.30");
+    assert_eq!(cash("CHF", "12.325", RoundingMode::HalfEven), "CHF 12.30");
+    assert_eq!(cash("CHF", "12.325", RoundingMode::HalfUp), "CHF 12.35");
+    assert_eq!(cash("CAD", "-0.03", RoundingMode::HalfEven), "CAD -0.05");
+    assert_eq!(cash("AUD", "9.97", RoundingMode::Floor), "AUD 9.95");
+    assert_eq!(cash("SEK", "12.33", RoundingMode::HalfEven), "SEK 12.00");
+    assert_eq!(cash("SEK", "12.5", RoundingMode::HalfUp), "SEK 13.00");
+    assert_eq!(cash("DKK", "7.26", RoundingMode::HalfEven), "DKK 7.50");
+
+    // Without a cash rule, cash is rounded to the minor unit
+    assert_eq!(cash("USD", "1.005", RoundingMode::HalfEven), "USD 1.00");
+    assert_eq!(cash("USD", "1.1", RoundingMode::HalfEven), "USD 1.10");
+    assert_eq!(cash("JPY", "99.5", RoundingMode::HalfEven), "JPY 100");
+    assert_eq!(amount("XAU", "1").round_cash(RoundingMode::HalfEven), Err(Error::NoMinorUnit("XAU".into())));
+
+    // Cash and electronic totals share an exponent, so they encode alike
+    let electronic = amount("CHF", "12.35");
+    let rounded = amount("CHF", "12.347").round_cash(RoundingMode::HalfEven).unwrap();
+    assert_eq!(rounded, electronic);
+    assert_eq!(rounded.to_cbor_data(), electronic.to_cbor_data());
+}
@@ -0,0 +1,191 @@
+use std::{ collections::HashMap, sync::{ Mutex, MutexGuard, Once } };
+
+use crate::{ CurrencyCode, CurrencyDefinition, Error, Result, iso4217::{ iso4217_entry, iso4217_entry_by_numeric } };
+
+/// The most minor units a currency can have: an `i64` count of minor units
+/// has at most 18 decimal places below the major unit, as ETH's wei do.
+const MAX_MINOR_UNITS: u8 = 18;
+
+/// A registry of currencies beyond ISO 4217: cryptocurrencies, loyalty
+/// points, internal settlement units and the like.
+///
+/// Lookups consult the embedded ISO 4217 table first and then the custom
+/// definitions, so ISO codes can't be shadowed. The process-wide store used
+/// by [`CurrencyCode`](crate::CurrencyCode) is accessed with the
+/// [`with_currencies!`](crate::with_currencies) and
+/// [`with_currencies_mut!`](crate::with_currencies_mut) macros, in the same
+/// way dcbor's global `TagsStore` is.
+#[derive(Clone, Debug, Default)]
+pub struct CurrenciesStore {
+    definitions_by_code: HashMap<String, CurrencyDefinition>,
+    codes_by_numeric: HashMap<u16, String>,
+}
+
+impl CurrenciesStore {
+    pub fn new<T>(definitions: T) -> Result<Self> where T: IntoIterator<Item = CurrencyDefinition> {
+        let mut store = Self::default();
+        for definition in definitions {
+            store.insert(definition)?;
+        }
+        Ok(store)
+    }
+
+    /// Registers a custom currency.
+    ///
+    /// The code must be 2 to 10 upper-case ASCII letters or digits, and the
+    /// minor units at most 18. Fails if the code or numeric code is already
+    /// used by ISO 4217, or by a different custom definition. Registering an
+    /// identical definition again is allowed.
+    pub fn insert(&mut self, definition: CurrencyDefinition) -> Result<()> {
+        let code = definition.code();
+        if !CurrencyCode::is_well_formed(code) {
+            return Err(Error::InvalidCurrencyCode(code.to_string()));
+        }
+        if let Some(minor_units) = definition.minor_units()
+            && minor_units > MAX_MINOR_UNITS
+        {
+            return Err(Error::InvalidMinorUnits { code: code.to_string(), minor_units });
+        }
+
+        if iso4217_entry(code).is_some() {
+            return Err(Error::CurrencyCollision(code.to_string()));
+        }
+        if let Some(existing) = self.definitions_by_code.get(code) {
+            return if *existing == definition {
+                Ok(())
+            } else {
+                Err(Error::CurrencyCollision(code.to_string()))
+            };
+        }
+        if let Some(numeric) = definition.numeric_code() {
+            if iso4217_entry_by_numeric(numeric).is_some()
+                || self.codes_by_numeric.contains_key(&numeric)
+            {
+                return Err(Error::CurrencyCollision(format!("{} ({})", code, numeric)));
+            }
+            self.codes_by_numeric.insert(numeric, code.to_string());
+        }
+
+        self.definitions_by_code.insert(code.to_string(), definition);
+        Ok(())
+    }
+
+    pub fn insert_all(&mut self, definitions: Vec<CurrencyDefinition>) -> Result<()> {
+        for definition in definitions {
+            self.insert(definition)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the definition for `code`, from ISO 4217 or the custom
+    /// definitions. The code must be in canonical (upper case) form.
+    pub fn definition(&self, code: &str) -> Option<CurrencyDefinition> {
+        iso4217_entry(code)
+            .map(CurrencyDefinition::from)
+            .or_else(|| self.definitions_by_code.get(code).cloned())
+    }
+
+    /// Returns the definition with the given numeric code, from ISO 4217 or
+    /// the custom definitions.
+    pub fn definition_for_numeric_code(&self, numeric: u16) -> Option<CurrencyDefinition> {
+        iso4217_entry_by_numeric(numeric)
+            .map(CurrencyDefinition::from)
+            .or_else(|| {
+                let code = self.codes_by_numeric.get(&numeric)?;
+                self.definitions_by_code.get(code).cloned()
+            })
+    }
+}
+
+pub struct LazyCurrenciesStore {
+    init: Once,
+    data: Mutex<Option<CurrenciesStore>>,
+}
+
+impl LazyCurrenciesStore {
+    pub fn get(&self) -> MutexGuard<'_, Option<CurrenciesStore>> {
+        self.init.call_once(|| {
+            *self.data.lock().unwrap() = Some(CurrenciesStore::default());
+        });
+        self.data.lock().unwrap()
+    }
+}
+
+pub static GLOBAL_CURRENCIES: LazyCurrenciesStore = LazyCurrenciesStore {
+    init: Once::new(),
+    data: Mutex::new(None),
+};
+
+/// Runs a closure with read access to the global [`CurrenciesStore`].
+///
+/// The store is locked for the duration of the closure, so the closure must
+/// not call anything that consults the store itself, such as
+/// `CurrencyCode::definition`.
+#[macro_export]
+macro_rules! with_currencies {
+    ($action:expr) => {
+        {
+        let binding = $crate::GLOBAL_CURRENCIES.get();
+        let currencies = binding.as_ref().unwrap();
+        #[allow(clippy::redundant_closure_call)]
+        $action(currencies)
+        }
+    };
+}
+
+/// Runs a closure with write access to the global [`CurrenciesStore`].
+#[macro_export]
+macro_rules! with_currencies_mut {
+    ($action:expr) => {
+        {
+        let mut binding = $crate::GLOBAL_CURRENCIES.get();
+        let currencies = binding.as_mut().unwrap();
+        #[allow(clippy::redundant_closure_call)]
+        $action(currencies)
+        }
+    };
+}
+
+#[test]
+fn currencies_store() {
+    let btc = CurrencyDefinition::new("BTC", None, Some(8), Some("₿"), "Bitcoin");
+    let eth = CurrencyDefinition::new("ETH", None, Some(18), Some("Ξ"), "Ether");
+    let points = CurrencyDefinition::new("POINTS", Some(9001), Some(0), None, "Loyalty Points");
+    let mut store = CurrenciesStore::new([btc.clone(), eth.clone()]).unwrap();
+    store.insert(points.clone()).unwrap();
+
+    assert_eq!(store.definition("BTC"), Some(btc.clone()));
+    assert_eq!(store.definition("ETH").unwrap().minor_units(), Some(18));
+    assert_eq!(store.definition("POINTS"), Some(points));
+    assert_eq!(store.definition("USD").unwrap().numeric_code(), Some(840));
+    assert_eq!(store.definition("btc"), None);
+    assert_eq!(store.definition_for_numeric_code(9001).unwrap().code(), "POINTS");
+    assert_eq!(store.definition_for_numeric_code(840).unwrap().code(), "USD");
+    assert_eq!(store.definition_for_numeric_code(9002), None);
+
+    // Re-registering the same definition is fine
+    store.insert(btc).unwrap();
+
+    // Collisions with ISO 4217 or earlier definitions are rejected
+    let collision = |code: &str, numeric| CurrencyDefinition::new(code, numeric, Some(2), None, "Fake");
+    assert_eq!(store.insert(collision("USD", None)), Err(Error::CurrencyCollision("USD".into())));
+    assert_eq!(store.insert(collision("BTC", None)), Err(Error::CurrencyCollision("BTC".into())));
+    assert_eq!(store.insert(collision("FAKE", Some(840))), Err(Error::CurrencyCollision("FAKE (840)".into())));
+    assert_eq!(store.insert(collision("FAKE", Some(9001))), Err(Error::CurrencyCollision("FAKE (9001)".into())));
+    assert!(store.definition("FAKE").is_none());
+
+    // Codes must be upper-case letters and digits
+    for code in ["btc", "X", "ELEVENCHARS", "US D", ""] {
+        assert_eq!(store.insert(collision(code, None)), Err(Error::InvalidCurrencyCode(code.into())));
+    }
+
+    // Minor units beyond 18 would overflow the minor-unit exponent
+    for minor_units in [19, 128, 200] {
+        let definition = CurrencyDefinition::new("WIDE", None, Some(minor_units), None, "Too Wide");
+        assert_eq!(
+            store.insert(definition),
+            Err(Error::InvalidMinorUnits { code: "WIDE".into(), minor_units })
+        );
+    }
+    assert!(store.definition("WIDE").is_none());
+}
@@ -1,5 +1,5 @@
-use dcbor::prelude::*;
-use crate::{ CurrencyCode, DecimalFraction, TAG_CURRENCY_AMOUNT };
+use dcbor::{ Date, prelude::* };
+use crate::{ CurrencyCode, CurrencyCodeEncoding, DecimalFraction, Error, Result, RoundingMode, TAG_CURRENCY_AMOUNT };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 // ANCHOR: example_14
@@ -50,3 +50,346 @@ impl std::fmt::Display for CurrencyAmount {
     }
 }
 // ANCHOR_END: example_14
+
+impl CurrencyAmount {
+    /// Decodes like `TryFrom<CBOR>`, but validates the currency code as
+    /// [`CurrencyCode::try_from_strict`] does.
+    pub fn try_from_strict(cbor: CBOR) -> Result<Self, dcbor::Error> {
+        let amount = Self::try_from(cbor)?;
+        CurrencyCode::known_definition(amount.currency().code())?;
+        Ok(amount)
+    }
+
+    /// Decodes like [`CurrencyAmount::try_from_strict`], and also returns a
+    /// warning if the currency code wasn't valid on `date`, as
+    /// [`CurrencyCode::decode_as_of`] does.
+    pub fn decode_as_of(cbor: CBOR, date: &Date) -> Result<(Self, Vec<Error>), dcbor::Error> {
+        let amount = Self::try_from(cbor)?;
+        let warnings = amount.currency().decoding_warnings(date)?;
+        Ok((amount, warnings))
+    }
+
+    /// Encodes like `From<CurrencyAmount> for CBOR`, with the currency code
+    /// in the given form.
+    pub fn to_cbor_with(&self, encoding: CurrencyCodeEncoding) -> CBOR {
+        let v = vec![self.currency().to_cbor_with(encoding), self.amount().to_cbor()].to_cbor();
+        CBOR::to_tagged_value(TAG_CURRENCY_AMOUNT, v)
+    }
+
+    /// A zero amount in `currency`, the starting point for a fold.
+    pub fn zero(currency: CurrencyCode) -> Self {
+        Self(currency, DecimalFraction::new(0, 0))
+    }
+
+    /// Fails with [`Error::CurrencyMismatch`] unless `other` is in the same
+    /// currency.
+    fn check_currency(&self, other: &Self) -> Result<()> {
+        if self.0 != other.0 {
+            return Err(Error::CurrencyMismatch {
+                left: self.0.to_string(),
+                right: other.0.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Exact addition of two amounts in the same currency.
+    pub fn checked_add(&self, other: &Self) -> Result<Self> {
+        self.check_currency(other)?;
+        Ok(Self(self.0.clone(), self.1.checked_add(other.1)?))
+    }
+
+    /// Exact subtraction of two amounts in the same currency.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self> {
+        self.check_currency(other)?;
+        Ok(Self(self.0.clone(), self.1.checked_sub(other.1)?))
+    }
+
+    /// Exact multiplication by a scalar, such as a quantity or a tax rate.
+    /// The result usually has more decimal places than the currency's minor
+    /// unit; see [`DecimalFraction::rescale`].
+    pub fn checked_mul(&self, factor: DecimalFraction) -> Result<Self> {
+        Ok(Self(self.0.clone(), self.1.checked_mul(factor)?))
+    }
+
+    pub fn checked_neg(&self) -> Result<Self> {
+        Ok(Self(self.0.clone(), self.1.checked_neg()?))
+    }
+
+    /// The absolute value. Fails only for a mantissa of `i64::MIN`.
+    pub fn abs(&self) -> Result<Self> {
+        if self.is_negative() { self.checked_neg() } else { Ok(self.clone()) }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.1.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.1.mantissa < 0
+    }
+
+    /// Divides this amount into shares proportional to `ratios`, losing
+    /// nothing: the shares always add up to exactly this amount.
+    ///
+    /// Shares are expressed in the currency's minor units (or with this
+    /// amount's own scale, if that is finer or the currency is unknown), and
+    /// all have the same exponent, so equal shares encode to identical CBOR.
+    /// Units left over after rounding every share toward zero go one at a
+    /// time to the shares with the largest remainders, ties going to the
+    /// earliest, so the result depends only on the inputs.
+    pub fn allocate(&self, ratios: &[u64]) -> Result<Vec<Self>> {
+        let total_ratio: u128 = ratios.iter().map(|&r| r as u128).sum();
+        if total_ratio == 0 {
+            return Err(Error::InvalidAllocation("ratios must not all be zero".into()));
+        }
+
+        let amount = self.allocation_amount()?;
+        let units = amount.mantissa.unsigned_abs() as u128;
+
+        let mut shares: Vec<u128> = Vec::with_capacity(ratios.len());
+        let mut remainders: Vec<(u128, usize)> = Vec::with_capacity(ratios.len());
+        for (i, &ratio) in ratios.iter().enumerate() {
+            // `units < 2^64` and `ratio < 2^64`, so this can't overflow.
+            let n = units * ratio as u128;
+            shares.push(n / total_ratio);
+            remainders.push((n % total_ratio, i));
+        }
+
+        let leftover = units - shares.iter().sum::<u128>();
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for &(_, i) in remainders.iter().take(leftover as usize) {
+            shares[i] += 1;
+        }
+
+        Ok(shares.into_iter().map(|share| self.share(amount, share)).collect())
+    }
+
+    /// Divides this amount into `n` shares that differ by at most one minor
+    /// unit, like [`CurrencyAmount::allocate`] with equal ratios: every share
+    /// gets `units / n`, and the first `units % n` shares one more.
+    pub fn split(&self, n: usize) -> Result<Vec<Self>> {
+        if n == 0 {
+            return Err(Error::InvalidAllocation("cannot split into zero shares".into()));
+        }
+
+        let amount = self.allocation_amount()?;
+        let units = amount.mantissa.unsigned_abs() as u128;
+        let (quotient, remainder) = (units / n as u128, units % n as u128);
+
+        let mut shares = Vec::new();
+        shares
+            .try_reserve_exact(n)
+            .map_err(|_| Error::InvalidAllocation(format!("cannot split into {} shares", n)))?;
+        shares.extend((0..n).map(|i| self.share(amount, quotient + ((i as u128) < remainder) as u128)));
+        Ok(shares)
+    }
+
+    /// This amount at the scale shares of it are expressed in: the
+    /// currency's minor units, or this amount's own scale if that is finer
+    /// or the currency is unknown.
+    fn allocation_amount(&self) -> Result<DecimalFraction> {
+        let scale = (-(self.1.exponent as i16)).max(self.0.minor_units().unwrap_or(0) as i16);
+        let exponent = i8::try_from(-scale).map_err(|_| Error::ExponentOverflow)?;
+        self.1.quantize(exponent, RoundingMode::HalfEven)
+    }
+
+    /// A share of `share` units of `amount`, with its sign and exponent.
+    fn share(&self, amount: DecimalFraction, share: u128) -> Self {
+        // Each share is at most `units`, which came from an `i64`.
+        let share = share as i128;
+        let mantissa = (if amount.mantissa < 0 { -share } else { share }) as i64;
+        Self(self.0.clone(), DecimalFraction::new(amount.exponent, mantissa))
+    }
+
+    /// An amount given as a whole number of `currency`'s minor units, such
+    /// as cents: `from_minor_units(USD, 110)` is `USD 1.10`.
+    pub fn from_minor_units(currency: CurrencyCode, units: i64) -> Result<Self> {
+        let minor_units = currency.minor_units().ok_or_else(|| Error::NoMinorUnit(currency.to_string()))?;
+        let minor_units = i8::try_from(minor_units).map_err(|_| Error::ExponentOverflow)?;
+        Ok(Self(currency, DecimalFraction::new(-minor_units, units)))
+    }
+
+    /// This amount as a whole number of its currency's minor units, such as
+    /// cents: `USD 1.1` is 110.
+    ///
+    /// Trailing zeros beyond the minor unit are fine (`USD 1.100` is also
+    /// 110), but nonzero digits are never rounded away: `USD 1.105` fails
+    /// with [`Error::ExcessPrecision`]. Round first with
+    /// [`DecimalFraction::rescale`] if that's intended.
+    pub fn to_minor_units(&self) -> Result<i64> {
+        let minor_units = self.0.minor_units().ok_or_else(|| Error::NoMinorUnit(self.0.to_string()))?;
+        let scale = i8::try_from(minor_units).map_err(|_| Error::ExponentOverflow)?;
+        let exact = self.1.rescale(scale, RoundingMode::TowardZero)?;
+        if !exact.numeric_eq(&self.1) {
+            return Err(Error::ExcessPrecision { amount: self.to_string(), minor_units });
+        }
+        Ok(exact.mantissa)
+    }
+}
+
+impl std::ops::Add for CurrencyAmount {
+    type Output = Result<Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(&rhs)
+    }
+}
+
+impl std::ops::Sub for CurrencyAmount {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(&rhs)
+    }
+}
+
+impl std::ops::Mul<DecimalFraction> for CurrencyAmount {
+    type Output = Result<Self>;
+
+    fn mul(self, rhs: DecimalFraction) -> Self::Output {
+        self.checked_mul(rhs)
+    }
+}
+
+impl std::ops::Neg for CurrencyAmount {
+    type Output = Result<Self>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+impl std::iter::Sum<CurrencyAmount> for Result<CurrencyAmount> {
+    /// Sums amounts in a single currency. An empty iterator fails with
+    /// [`Error::EmptyCurrencyAmountSum`], since there is no currency for the
+    /// result; fold from [`CurrencyAmount::zero`] to get a zero instead.
+    fn sum<I: Iterator<Item = CurrencyAmount>>(mut iter: I) -> Self {
+        let first = iter.next().ok_or(Error::EmptyCurrencyAmountSum)?;
+        iter.try_fold(first, |acc, x| acc.checked_add(&x))
+    }
+}
+
+impl<'a> std::iter::Sum<&'a CurrencyAmount> for Result<CurrencyAmount> {
+    fn sum<I: Iterator<Item = &'a CurrencyAmount>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// Builds an amount from a currency code and a decimal string, for tests.
+#[cfg(test)]
+pub(crate) fn amount(code: &str, s: &str) -> CurrencyAmount {
+    CurrencyAmount::new(CurrencyCode::new(code), s.parse().unwrap())
+}
+
+#[test]
+fn currency_amount_arithmetic() {
+    let eur = CurrencyAmount::new(CurrencyCode::new("EUR"), DecimalFraction::new(0, 1));
+
+    assert_eq!(amount("USD", "1.10").checked_add(&amount("USD", "2.5")).unwrap().to_string(), "USD 3.60");
+    assert_eq!((amount("USD", "1.10") - amount("USD", "2.5")).unwrap().to_string(), "USD -1.40");
+    assert_eq!(
+        amount("USD", "1").checked_add(&eur),
+        Err(Error::CurrencyMismatch { left: "USD".into(), right: "EUR".into() })
+    );
+    assert!((eur.clone() - amount("USD", "1")).is_err());
+
+    // Scalar multiplication, e.g. a quantity or a tax rate
+    let tax = amount("USD", "19.99").checked_mul("0.0825".parse().unwrap()).unwrap();
+    assert_eq!(tax.to_string(), "USD 1.649175");
+    assert_eq!((amount("USD", "2.50") * DecimalFraction::new(0, 3)).unwrap().to_string(), "USD 7.50");
+
+    let negative = (-amount("USD", "2.50")).unwrap();
+    assert!(negative.is_negative());
+    assert_eq!(negative.abs().unwrap(), amount("USD", "2.50"));
+    assert_eq!(amount("USD", "2.50").abs().unwrap(), amount("USD", "2.50"));
+    assert!(amount("USD", "0.00").is_zero());
+    assert!(!amount("USD", "0.00").is_negative());
+    let min = CurrencyAmount::new(CurrencyCode::new("USD"), DecimalFraction::new(0, i64::MIN));
+    assert_eq!(min.abs(), Err(Error::MantissaOverflow));
+}
+
+#[test]
+fn currency_amount_sum() {
+    let amounts = [amount("USD", "1.25"), amount("USD", "2.50"), amount("USD", "-0.75")];
+
+    let total: Result<CurrencyAmount> = amounts.iter().sum();
+    assert_eq!(total.unwrap().to_string(), "USD 3.00");
+
+    let empty: Result<CurrencyAmount> = std::iter::empty::<CurrencyAmount>().sum();
+    assert_eq!(empty, Err(Error::EmptyCurrencyAmountSum));
+    let zero = amounts[..0]
+        .iter()
+        .try_fold(CurrencyAmount::zero(CurrencyCode::new("USD")), |acc, x| acc.checked_add(x));
+    assert!(zero.unwrap().is_zero());
+
+    let mixed = [amount("USD", "1"), CurrencyAmount::new(CurrencyCode::new("EUR"), DecimalFraction::new(0, 1))];
+    assert!(matches!(mixed.into_iter().sum::<Result<CurrencyAmount>>(), Err(Error::CurrencyMismatch { .. })));
+}
+
+#[test]
+fn currency_amount_allocate() {
+    let strings = |shares: Vec<CurrencyAmount>| shares.iter().map(|s| s.amount().to_string()).collect::<Vec<_>>();
+
+    // USD 100 three ways, padded to cents
+    let shares = amount("USD", "100").split(3).unwrap();
+    assert_eq!(strings(shares.clone()), ["33.34", "33.33", "33.33"]);
+    assert_eq!(shares.iter().sum::<Result<CurrencyAmount>>().unwrap().amount().to_string(), "100.00");
+    assert_eq!(shares[1].to_cbor_data(), shares[2].to_cbor_data());
+
+    // Leftover units go to the largest remainders first, then the earliest
+    assert_eq!(strings(amount("USD", "0.07").allocate(&[1, 2]).unwrap()), ["0.02", "0.05"]);
+    assert_eq!(strings(amount("USD", "0.05").allocate(&[3, 7]).unwrap()), ["0.02", "0.03"]);
+    let shares = amount("USD", "10").allocate(&[1, 0, 2]).unwrap();
+    assert_eq!(shares[1].amount(), &DecimalFraction::new(-2, 0));
+    assert_eq!(strings(shares), ["3.33", "0", "6.67"]);
+    assert_eq!(strings(amount("USD", "-100").split(3).unwrap()), ["-33.34", "-33.33", "-33.33"]);
+
+    // Minor units vary by currency, and a finer amount keeps its scale
+    assert_eq!(strings(amount("JPY", "100").split(3).unwrap()), ["34", "33", "33"]);
+    assert_eq!(strings(amount("BHD", "1").split(3).unwrap()), ["0.334", "0.333", "0.333"]);
+    assert_eq!(strings(amount("USD", "0.001").split(2).unwrap()), ["0.001", "0"]);
+    assert_eq!(strings(amount("ABC", "5").split(2).unwrap()), ["3", "2"]);
+
+    assert_eq!(strings(amount("USD", "0.05").split(7).unwrap()), ["0.01", "0.01", "0.01", "0.01", "0.01", "0", "0"]);
+    assert_eq!(
+        amount("USD", "100").split(3).unwrap(),
+        amount("USD", "100").allocate(&[1, 1, 1]).unwrap()
+    );
+
+    assert!(matches!(amount("USD", "1").split(0), Err(Error::InvalidAllocation(_))));
+    assert!(matches!(amount("USD", "1").split(usize::MAX), Err(Error::InvalidAllocation(_))));
+    assert!(matches!(amount("USD", "1").allocate(&[0, 0]), Err(Error::InvalidAllocation(_))));
+    assert!(matches!(amount("USD", "1").allocate(&[]), Err(Error::InvalidAllocation(_))));
+}
+
+#[test]
+fn currency_amount_minor_units() {
+    let from = |code: &str, units| CurrencyAmount::from_minor_units(CurrencyCode::new(code), units);
+
+    assert_eq!(amount("USD", "1.1").to_minor_units(), Ok(110));
+    assert_eq!(amount("USD", "-1.100").to_minor_units(), Ok(-110));
+    assert_eq!(amount("USD", "12").to_minor_units(), Ok(1200));
+    assert_eq!(amount("JPY", "1234").to_minor_units(), Ok(1234));
+    assert_eq!(amount("BHD", "1.5").to_minor_units(), Ok(1500));
+    assert_eq!(CurrencyAmount::new(CurrencyCode::new("JPY"), DecimalFraction::new(2, 5)).to_minor_units(), Ok(500));
+
+    assert_eq!(from("USD", 110).unwrap().to_string(), "USD 1.10");
+    assert_eq!(from("JPY", -7).unwrap().to_string(), "JPY -7");
+    assert_eq!(from("BHD", 1).unwrap().to_string(), "BHD 0.001");
+    for units in [0, 1, -99, i64::MAX, i64::MIN] {
+        assert_eq!(from("EUR", units).unwrap().to_minor_units(), Ok(units));
+    }
+
+    // Never truncates
+    assert_eq!(
+        amount("USD", "1.105").to_minor_units(),
+        Err(Error::ExcessPrecision { amount: "USD 1.105".into(), minor_units: 2 })
+    );
+    assert!(amount("JPY", "0.5").to_minor_units().is_err());
+    let huge = CurrencyAmount::new(CurrencyCode::new("USD"), DecimalFraction::new(0, i64::MAX));
+    assert_eq!(huge.to_minor_units(), Err(Error::MantissaOverflow));
+
+    assert_eq!(amount("XAU", "1").to_minor_units(), Err(Error::NoMinorUnit("XAU".into())));
+    assert_eq!(from("ABC", 1), Err(Error::NoMinorUnit("ABC".into())));
+}
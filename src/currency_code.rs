@@ -1,8 +1,10 @@
-use dcbor::prelude::*;
+use dcbor::{ Date, prelude::* };
 
-use crate::TAG_CURRENCY_CODE;
+use crate::{ CurrenciesStore, CurrencyDefinition, Error, Result, TAG_CURRENCY_CODE, with_currencies };
+#[cfg(test)]
+use crate::{ CurrencyAmount, DecimalFraction, TAG_CURRENCY_AMOUNT };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 // ANCHOR: example_11
 pub struct CurrencyCode(String);
 
@@ -27,7 +29,13 @@ impl TryFrom<CBOR> for CurrencyCode {
 
     fn try_from(cbor: CBOR) -> Result<Self, Self::Error> {
         let value = cbor.try_into_expected_tagged_value(TAG_CURRENCY_CODE)?;
-        let currency_code: String = value.try_into()?;
+        let currency_code: String = match value.as_case() {
+            CBORCase::Unsigned(numeric) => {
+                let numeric = u16::try_from(*numeric).map_err(|_| Error::UnknownNumericCurrencyCode(*numeric))?;
+                CurrencyCode::from_numeric_code(numeric)?.0
+            }
+            _ => value.try_into()?,
+        };
         Ok(CurrencyCode(currency_code))
     }
 }
@@ -38,3 +46,366 @@ impl std::fmt::Display for CurrencyCode {
     }
 }
 // ANCHOR_END: example_11
+
+/// Which form [`CurrencyCode::to_cbor_with`] emits.
+///
+/// `From<CurrencyCode> for CBOR` always emits the alphabetic form, and
+/// decoding always accepts both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CurrencyCodeEncoding {
+    /// The alphabetic code as a text string, like `33000("USD")`.
+    #[default]
+    Alphabetic,
+    /// The numeric code as an unsigned integer, like `33000(840)`, which is
+    /// a byte shorter and needs no string handling to decode. Codes without
+    /// a numeric code are still emitted as text.
+    Numeric,
+}
+
+impl CurrencyCode {
+    /// Creates a `CurrencyCode` from a known ISO 4217 code or registered
+    /// custom currency, normalizing it to upper case, so `"usd"` becomes
+    /// `"USD"`. Unlike [`CurrencyCode::new`], rejects anything that isn't in
+    /// the ISO 4217 table or the global [`CurrenciesStore`], or that isn't
+    /// valid today.
+    pub fn try_new(code: &str) -> Result<Self> {
+        Self::try_new_as_of(code, &Date::now())
+    }
+
+    /// Like [`CurrencyCode::try_new`], but checks that the code was valid on
+    /// `date`, so withdrawn codes like `"DEM"` are accepted for dates before
+    /// their withdrawal.
+    pub fn try_new_as_of(code: &str, date: &Date) -> Result<Self> {
+        let code = code.to_ascii_uppercase();
+        Self::validate(&code, date)?;
+        Ok(Self(code))
+    }
+
+    /// Checks that `code` is exactly a known upper-case ISO 4217 code or a
+    /// registered custom code, valid on `date`.
+    fn validate(code: &str, date: &Date) -> Result<()> {
+        Self::known_definition(code)?.validate_as_of(date)
+    }
+
+    /// Returns the definition of `code` if it is exactly a known upper-case
+    /// ISO 4217 code or a registered custom code, whatever its validity
+    /// dates. Unknown codes that are [well formed](Self::is_well_formed) are
+    /// reported as unknown; anything else as invalid.
+    pub(crate) fn known_definition(code: &str) -> Result<CurrencyDefinition> {
+        if let Some(definition) = with_currencies!(|store: &CurrenciesStore| store.definition(code)) {
+            return Ok(definition);
+        }
+        if !Self::is_well_formed(code) {
+            return Err(Error::InvalidCurrencyCode(code.to_string()));
+        }
+        Err(Error::UnknownCurrencyCode(code.to_string()))
+    }
+
+    /// Whether `code` has the form of an ISO 4217 or custom currency code: 2
+    /// to 10 upper-case ASCII letters or digits.
+    pub(crate) fn is_well_formed(code: &str) -> bool {
+        (2..=10).contains(&code.len()) && code.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+    }
+
+    /// Decodes like `TryFrom<CBOR>`, but rejects anything that isn't exactly
+    /// a known upper-case code, so lower-case codes are rejected rather than
+    /// normalized. Use this where garbage codes must never get through.
+    ///
+    /// Archival data legitimately carries codes that are no longer (or not
+    /// yet) valid, so validity dates are not checked; use
+    /// [`CurrencyCode::decode_as_of`] to be told about those.
+    pub fn try_from_strict(cbor: CBOR) -> Result<Self, dcbor::Error> {
+        let code = Self::try_from(cbor)?;
+        Self::known_definition(&code.0)?;
+        Ok(code)
+    }
+
+    /// Decodes like [`CurrencyCode::try_from_strict`], and also returns a
+    /// warning, such as [`Error::WithdrawnCurrencyCode`], if the code wasn't
+    /// valid on `date`.
+    pub fn decode_as_of(cbor: CBOR, date: &Date) -> Result<(Self, Vec<Error>), dcbor::Error> {
+        let code = Self::try_from(cbor)?;
+        let warnings = code.decoding_warnings(date)?;
+        Ok((code, warnings))
+    }
+
+    /// The check made by [`CurrencyCode::decode_as_of`]: unknown codes are
+    /// errors, and codes not valid on `date` are warnings.
+    pub(crate) fn decoding_warnings(&self, date: &Date) -> Result<Vec<Error>> {
+        match Self::known_definition(&self.0)?.validate_as_of(date) {
+            Ok(()) => Ok(Vec::new()),
+            Err(warning) => Ok(vec![warning]),
+        }
+    }
+
+    /// Returns `true` if this is a known ISO 4217 or registered custom code in
+    /// canonical (upper case) form that is valid today.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_as_of(&Date::now())
+    }
+
+    /// Checks that this is a known code in canonical form that was valid on
+    /// `date`.
+    pub fn validate_as_of(&self, date: &Date) -> Result<()> {
+        Self::validate(&self.0, date)
+    }
+
+    /// Returns `true` if this is a known code in canonical form that was
+    /// valid on `date`.
+    pub fn is_valid_as_of(&self, date: &Date) -> bool {
+        self.validate_as_of(date).is_ok()
+    }
+
+    /// Looks up a currency by its numeric code, in the ISO 4217 table or the
+    /// global [`CurrenciesStore`].
+    pub fn from_numeric_code(numeric: u16) -> Result<Self> {
+        with_currencies!(|store: &CurrenciesStore| store.definition_for_numeric_code(numeric))
+            .map(|definition| Self(definition.code().to_string()))
+            .ok_or(Error::UnknownNumericCurrencyCode(numeric.into()))
+    }
+
+    /// Encodes this code in the given form. With
+    /// [`CurrencyCodeEncoding::Numeric`], codes without a numeric code are
+    /// still emitted as text, as are codes whose numeric code has been reused
+    /// by a later currency (ANG's 532 decodes as XCG), so the numeric form
+    /// always decodes back to the same code.
+    pub fn to_cbor_with(&self, encoding: CurrencyCodeEncoding) -> CBOR {
+        if encoding == CurrencyCodeEncoding::Numeric
+            && let Some(numeric) = self.numeric_code()
+            && Self::from_numeric_code(numeric).as_ref() == Ok(self)
+        {
+            return CBOR::to_tagged_value(TAG_CURRENCY_CODE, numeric);
+        }
+        self.to_cbor()
+    }
+
+    /// Returns the metadata for this currency from the ISO 4217 table or the
+    /// global [`CurrenciesStore`], or `None` if the code is unknown.
+    pub fn definition(&self) -> Option<CurrencyDefinition> {
+        with_currencies!(|store: &CurrenciesStore| store.definition(&self.0))
+    }
+
+    /// The number of decimal places of the minor unit (USD: 2, JPY: 0, BHD:
+    /// 3), or `None` if the code is unknown or has no minor unit.
+    pub fn minor_units(&self) -> Option<u8> {
+        self.definition()?.minor_units()
+    }
+
+    /// The ISO 4217 numeric code (USD: 840), or `None` if the code is
+    /// unknown.
+    pub fn numeric_code(&self) -> Option<u16> {
+        self.definition()?.numeric_code()
+    }
+
+    /// The local symbol (USD: `"$"`), or `None` if the code is unknown or
+    /// has no symbol.
+    pub fn symbol(&self) -> Option<String> {
+        self.definition()?.symbol().map(Into::into)
+    }
+
+    /// The English name (USD: `"US Dollar"`), or `None` if the code is
+    /// unknown.
+    pub fn name(&self) -> Option<String> {
+        Some(self.definition()?.name().into())
+    }
+}
+
+#[test]
+fn currency_code_try_new() {
+    let date = Date::from_ymd(2025, 6, 30);
+    assert_eq!(CurrencyCode::try_new_as_of("USD", &date).unwrap().code(), "USD");
+    assert_eq!(CurrencyCode::try_new_as_of("usd", &date).unwrap().code(), "USD");
+    assert_eq!(CurrencyCode::try_new_as_of("eUr", &date).unwrap(), CurrencyCode::new("EUR"));
+
+    assert_eq!(CurrencyCode::try_new_as_of("", &date), Err(Error::InvalidCurrencyCode("".into())));
+    assert_eq!(CurrencyCode::try_new_as_of("DOLLARS", &date), Err(Error::UnknownCurrencyCode("DOLLARS".into())));
+    assert_eq!(CurrencyCode::try_new_as_of("U$D", &date), Err(Error::InvalidCurrencyCode("U$D".into())));
+    assert_eq!(CurrencyCode::try_new_as_of("DOLLARSIGNS", &date), Err(Error::InvalidCurrencyCode("DOLLARSIGNS".into())));
+    assert_eq!(CurrencyCode::try_new_as_of("ABC", &date), Err(Error::UnknownCurrencyCode("ABC".into())));
+
+    assert!(CurrencyCode::new("JPY").is_valid_as_of(&date));
+    assert!(!CurrencyCode::new("jpy").is_valid_as_of(&date));
+    assert!(!CurrencyCode::new("DOLLARS").is_valid_as_of(&date));
+}
+
+#[test]
+fn currency_code_metadata() {
+    let usd = CurrencyCode::new("USD");
+    assert_eq!(usd.minor_units(), Some(2));
+    assert_eq!(usd.numeric_code(), Some(840));
+    assert_eq!(usd.symbol().as_deref(), Some("$"));
+    assert_eq!(usd.name().as_deref(), Some("US Dollar"));
+
+    assert_eq!(CurrencyCode::new("JPY").minor_units(), Some(0));
+    assert_eq!(CurrencyCode::new("BHD").minor_units(), Some(3));
+    assert_eq!(CurrencyCode::new("CLF").minor_units(), Some(4));
+    assert_eq!(CurrencyCode::new("EUR").symbol().as_deref(), Some("€"));
+    assert_eq!(CurrencyCode::new("ALL").numeric_code(), Some(8));
+
+    // Gold has no minor unit and no symbol
+    let xau = CurrencyCode::new("XAU");
+    assert_eq!(xau.minor_units(), None);
+    assert_eq!(xau.symbol(), None);
+    assert_eq!(xau.name().as_deref(), Some("Gold"));
+
+    // Unknown or non-canonical codes have no metadata
+    assert_eq!(CurrencyCode::new("usd").definition(), None);
+    assert_eq!(CurrencyCode::new("ABC").minor_units(), None);
+}
+
+#[test]
+fn currency_code_numeric() {
+    assert_eq!(CurrencyCode::from_numeric_code(840).unwrap(), CurrencyCode::new("USD"));
+    assert_eq!(CurrencyCode::from_numeric_code(999).unwrap(), CurrencyCode::new("XXX"));
+    assert_eq!(CurrencyCode::from_numeric_code(1), Err(Error::UnknownNumericCurrencyCode(1)));
+
+    // The compact form always decodes
+    let compact = CBOR::to_tagged_value(TAG_CURRENCY_CODE, 978);
+    assert_eq!(compact.to_cbor_data().len(), 6);
+    assert_eq!(CurrencyCode::try_from(compact).unwrap(), CurrencyCode::new("EUR"));
+
+    for numeric in [0u64, 70000] {
+        let unknown = CBOR::to_tagged_value(TAG_CURRENCY_CODE, numeric);
+        assert!(CurrencyCode::try_from(unknown).is_err());
+    }
+    let negative = CBOR::to_tagged_value(TAG_CURRENCY_CODE, -840);
+    assert!(CurrencyCode::try_from(negative).is_err());
+}
+
+#[test]
+fn currency_code_encoding() {
+    let usd = CurrencyCode::new("USD");
+
+    // The alphabetic code is emitted unless the numeric form is requested
+    assert_eq!(usd.to_cbor().diagnostic_flat(), r#"33000("USD")"#);
+    assert_eq!(usd.to_cbor_data().len(), 7);
+    assert_eq!(usd.to_cbor_with(CurrencyCodeEncoding::Alphabetic), usd.to_cbor());
+
+    // The numeric form is shorter and round-trips
+    let numeric = usd.to_cbor_with(CurrencyCodeEncoding::Numeric);
+    assert_eq!(numeric.diagnostic_flat(), "33000(840)");
+    assert_eq!(numeric.to_cbor_data().len(), 6);
+    assert_eq!(CurrencyCode::try_from(numeric).unwrap(), usd);
+
+    // ...including inside a CurrencyAmount
+    let amount = CurrencyAmount::new(CurrencyCode::new("EUR"), DecimalFraction::new(-2, 1999));
+    let numeric = amount.to_cbor_with(CurrencyCodeEncoding::Numeric);
+    assert_eq!(numeric.diagnostic_flat(), "33001([33000(978), 4([-2, 1999])])");
+    assert_eq!(CurrencyAmount::try_from(numeric).unwrap(), amount);
+
+    // Codes without a numeric code fall back to text
+    let abc = CurrencyCode::new("ABC").to_cbor_with(CurrencyCodeEncoding::Numeric);
+    assert_eq!(abc.diagnostic_flat(), r#"33000("ABC")"#);
+
+    // ...as do codes whose numeric code now belongs to another currency
+    let ang = CurrencyCode::new("ANG").to_cbor_with(CurrencyCodeEncoding::Numeric);
+    assert_eq!(ang.diagnostic_flat(), r#"33000("ANG")"#);
+    let xcg = CurrencyCode::new("XCG").to_cbor_with(CurrencyCodeEncoding::Numeric);
+    assert_eq!(xcg.diagnostic_flat(), "33000(532)");
+
+    // Every code in the table round-trips in either form
+    for entry in crate::iso4217::ISO_4217 {
+        let code = CurrencyCode::new(entry.code);
+        for encoding in [CurrencyCodeEncoding::Alphabetic, CurrencyCodeEncoding::Numeric] {
+            assert_eq!(CurrencyCode::try_from(code.to_cbor_with(encoding)).unwrap(), code, "{:?}", encoding);
+        }
+    }
+}
+
+#[test]
+fn currency_code_as_of() {
+    let before_euro = Date::from_ymd(1999, 6, 30);
+    let today = Date::from_ymd(2026, 10, 17);
+
+    assert_eq!(CurrencyCode::try_new_as_of("dem", &before_euro).unwrap().code(), "DEM");
+    assert_eq!(
+        CurrencyCode::try_new_as_of("DEM", &today),
+        Err(Error::WithdrawnCurrencyCode { code: "DEM".into(), valid_until: "2002-03-01".into() })
+    );
+    assert!(matches!(CurrencyCode::try_new_as_of("FRF", &today), Err(Error::WithdrawnCurrencyCode { .. })));
+    assert!(!CurrencyCode::new("HRK").is_valid_as_of(&today));
+
+    // Bulgaria adopted the euro on 2026-01-01
+    let bgn = CurrencyCode::new("BGN");
+    assert!(bgn.is_valid_as_of(&Date::from_ymd(2025, 12, 31)));
+    assert!(!bgn.is_valid_as_of(&Date::from_ymd(2026, 1, 1)));
+
+    // XCG replaced ANG, reusing its numeric code
+    let xcg = CurrencyCode::new("XCG");
+    assert!(matches!(
+        xcg.validate_as_of(&Date::from_ymd(2020, 1, 1)),
+        Err(Error::CurrencyCodeNotYetValid { .. })
+    ));
+    assert!(xcg.is_valid_as_of(&today));
+    assert!(CurrencyCode::new("ANG").is_valid_as_of(&Date::from_ymd(2020, 1, 1)));
+    assert_eq!(CurrencyCode::from_numeric_code(532).unwrap(), xcg);
+
+    // The euro replaced its legacy currencies, which archived data still uses
+    assert!(matches!(
+        CurrencyCode::try_new_as_of("EUR", &Date::from_ymd(1990, 1, 1)),
+        Err(Error::CurrencyCodeNotYetValid { .. })
+    ));
+    assert!(CurrencyCode::new("EUR").is_valid_as_of(&Date::from_ymd(1999, 1, 1)));
+    let legacy = [
+        "ATS", "BEF", "CYP", "EEK", "ESP", "FIM", "GRD", "IEP", "ITL", "LTL", "LVL", "MTL", "NLG", "PTE", "SIT", "SKK",
+    ];
+    for code in legacy.into_iter().chain(["BYR", "MRO", "STD", "VEF"]) {
+        let currency = CurrencyCode::try_new_as_of(code, &Date::from_ymd(1990, 1, 1)).unwrap();
+        assert!(!currency.is_valid_as_of(&today), "{code}");
+    }
+    assert_eq!(CurrencyCode::from_numeric_code(380).unwrap().code(), "ITL");
+
+    // Withdrawn codes keep their metadata
+    let zwd = CurrencyCode::new("ZWD");
+    assert_eq!(zwd.name().as_deref(), Some("Zimbabwe Dollar"));
+    assert_eq!(zwd.definition().unwrap().valid_until().unwrap().to_string(), "2008-08-01");
+    assert_eq!(CurrencyCode::try_new_as_of("ABC", &before_euro), Err(Error::UnknownCurrencyCode("ABC".into())));
+}
+
+#[test]
+fn currency_code_strict_decoding() {
+    let garbage = ["usd", "", "DOLLARS", "ABC"].map(|code| CBOR::to_tagged_value(TAG_CURRENCY_CODE, code));
+
+    // Plain decoding accepts any string
+    for cbor in &garbage {
+        CurrencyCode::try_from(cbor.clone()).unwrap();
+    }
+
+    // Strict decoding rejects anything that isn't a known upper-case code
+    for cbor in &garbage {
+        assert!(CurrencyCode::try_from_strict(cbor.clone()).is_err(), "{}", cbor);
+    }
+    let usd = CurrencyCode::try_from_strict(CurrencyCode::new("USD").to_cbor()).unwrap();
+    assert_eq!(usd.code(), "USD");
+
+    // ...including inside a CurrencyAmount
+    let amount = CBOR::to_tagged_value(
+        TAG_CURRENCY_AMOUNT,
+        vec![CurrencyCode::new("usd").to_cbor(), DecimalFraction::new(-1, 11).to_cbor()],
+    );
+    assert!(CurrencyAmount::try_from(amount.clone()).is_ok());
+    assert!(CurrencyAmount::try_from_strict(amount).is_err());
+
+    // Withdrawn codes in archival data decode, with a warning if asked for
+    let archived = CurrencyAmount::new(CurrencyCode::new("DEM"), DecimalFraction::new(-2, 4999));
+    assert_eq!(CurrencyAmount::try_from_strict(archived.to_cbor()).unwrap(), archived);
+    let today = Date::from_ymd(2026, 10, 17);
+    let (decoded, warnings) = CurrencyAmount::decode_as_of(archived.to_cbor(), &today).unwrap();
+    assert_eq!(decoded, archived);
+    assert_eq!(
+        warnings,
+        vec![Error::WithdrawnCurrencyCode { code: "DEM".into(), valid_until: "2002-03-01".into() }]
+    );
+    let (_, warnings) = CurrencyAmount::decode_as_of(archived.to_cbor(), &Date::from_ymd(1999, 6, 30)).unwrap();
+    assert!(warnings.is_empty());
+
+    // ...including in the numeric form
+    let (frf, warnings) = CurrencyCode::decode_as_of(CBOR::to_tagged_value(TAG_CURRENCY_CODE, 250), &today).unwrap();
+    assert_eq!(frf.code(), "FRF");
+    assert_eq!(warnings.len(), 1);
+
+    // Unknown codes are still errors
+    for cbor in &garbage {
+        assert!(CurrencyCode::decode_as_of(cbor.clone(), &today).is_err(), "{}", cbor);
+    }
+}
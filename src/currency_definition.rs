@@ -0,0 +1,156 @@
+use dcbor::Date;
+
+use crate::{ DecimalFraction, Error, Result, cash_rounding::cash_increment, iso4217::Iso4217Entry };
+
+/// Metadata describing a currency: its codes, minor units, symbol, name, the
+/// period during which the code is valid, and how cash is rounded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurrencyDefinition {
+    code: String,
+    numeric_code: Option<u16>,
+    minor_units: Option<u8>,
+    symbol: Option<String>,
+    name: String,
+    valid_from: Option<Date>,
+    valid_until: Option<Date>,
+    cash_increment: Option<DecimalFraction>,
+}
+
+impl CurrencyDefinition {
+    pub fn new(
+        code: impl Into<String>,
+        numeric_code: Option<u16>,
+        minor_units: Option<u8>,
+        symbol: Option<&str>,
+        name: impl Into<String>
+    ) -> Self {
+        Self {
+            code: code.into(),
+            numeric_code,
+            minor_units,
+            symbol: symbol.map(Into::into),
+            name: name.into(),
+            valid_from: None,
+            valid_until: None,
+            cash_increment: None,
+        }
+    }
+
+    /// Sets the validity period. `valid_from` is the first day the code is
+    /// valid; `valid_until` is the day it was withdrawn, when it is no longer
+    /// valid. `None` leaves that end of the period open.
+    pub fn with_validity(mut self, valid_from: Option<Date>, valid_until: Option<Date>) -> Self {
+        self.valid_from = valid_from;
+        self.valid_until = valid_until;
+        self
+    }
+
+    /// Sets the smallest amount that can be paid in cash, such as 0.05 for
+    /// CHF, where it is coarser than the minor unit.
+    pub fn with_cash_increment(mut self, increment: DecimalFraction) -> Self {
+        self.cash_increment = Some(increment);
+        self
+    }
+
+    /// The alphabetic code, e.g. `"USD"`.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The ISO 4217 numeric code, e.g. `840` for USD.
+    pub fn numeric_code(&self) -> Option<u16> {
+        self.numeric_code
+    }
+
+    /// The number of decimal places of the minor unit: 2 for USD (cents), 0
+    /// for JPY, 3 for BHD. `None` for units like gold (XAU) that have no
+    /// minor unit.
+    pub fn minor_units(&self) -> Option<u8> {
+        self.minor_units
+    }
+
+    /// The local symbol, e.g. `"$"` or `"€"`, if there is one.
+    pub fn symbol(&self) -> Option<&str> {
+        self.symbol.as_deref()
+    }
+
+    /// The English name, e.g. `"US Dollar"`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The first day the code is valid, if known.
+    pub fn valid_from(&self) -> Option<&Date> {
+        self.valid_from.as_ref()
+    }
+
+    /// The day the code was withdrawn, or `None` if it is still active.
+    pub fn valid_until(&self) -> Option<&Date> {
+        self.valid_until.as_ref()
+    }
+
+    /// The smallest amount that can be paid in cash, if it is coarser than
+    /// the minor unit: 0.05 for CHF, 1 for SEK. `None` if cash is paid to
+    /// the minor unit.
+    pub fn cash_increment(&self) -> Option<DecimalFraction> {
+        self.cash_increment
+    }
+
+    /// Checks that the code is valid on `date`.
+    pub fn validate_as_of(&self, date: &Date) -> Result<()> {
+        if let Some(valid_from) = &self.valid_from && date < valid_from {
+            return Err(Error::CurrencyCodeNotYetValid {
+                code: self.code.clone(),
+                valid_from: valid_from.to_string(),
+            });
+        }
+        if let Some(valid_until) = &self.valid_until && date >= valid_until {
+            return Err(Error::WithdrawnCurrencyCode {
+                code: self.code.clone(),
+                valid_until: valid_until.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<&Iso4217Entry> for CurrencyDefinition {
+    fn from(entry: &Iso4217Entry) -> Self {
+        let date = |(year, month, day)| Date::from_ymd(year, month, day);
+        let definition = Self::new(entry.code, Some(entry.numeric), entry.minor_units, entry.symbol, entry.name)
+            .with_validity(entry.valid_from.map(date), entry.valid_until.map(date));
+        match cash_increment(entry.code) {
+            Some(increment) => definition.with_cash_increment(increment),
+            None => definition,
+        }
+    }
+}
+
+#[test]
+fn currency_definition_validity() {
+    let zwl = CurrencyDefinition::new("ZWL", Some(932), Some(2), Some("Z$"), "Zimbabwe Dollar")
+        .with_validity(Some(Date::from_ymd(2009, 2, 2)), Some(Date::from_ymd(2024, 9, 1)));
+
+    assert_eq!(zwl.valid_from().unwrap().to_string(), "2009-02-02");
+    assert!(zwl.validate_as_of(&Date::from_ymd(2009, 2, 2)).is_ok());
+    assert!(zwl.validate_as_of(&Date::from_ymd_hms(2024, 8, 31, 23, 59, 59)).is_ok());
+    assert_eq!(
+        zwl.validate_as_of(&Date::from_ymd(2024, 9, 1)),
+        Err(Error::WithdrawnCurrencyCode { code: "ZWL".into(), valid_until: "2024-09-01".into() })
+    );
+    assert_eq!(
+        zwl.validate_as_of(&Date::from_ymd(2000, 1, 1)),
+        Err(Error::CurrencyCodeNotYetValid { code: "ZWL".into(), valid_from: "2009-02-02".into() })
+    );
+
+    // Open-ended periods
+    let usd = CurrencyDefinition::new("USD", Some(840), Some(2), Some("$"), "US Dollar");
+    assert!(usd.validate_as_of(&Date::from_ymd(1900, 1, 1)).is_ok());
+    assert!(usd.valid_until().is_none());
+    assert_eq!(usd.cash_increment(), None);
+
+    let chf = CurrencyDefinition::from(crate::iso4217::iso4217_entry("CHF").unwrap());
+    assert_eq!(chf.cash_increment(), Some(DecimalFraction::new(-2, 5)));
+    let custom = usd.with_cash_increment(DecimalFraction::new(-1, 1));
+    assert_eq!(custom.cash_increment(), Some(DecimalFraction::new(-1, 1)));
+}
@@ -0,0 +1,205 @@
+use crate::{
+    CurrencyAmount,
+    DecimalFormat,
+    Locale,
+    locale::{ CurrencyPosition, LocaleData },
+};
+#[cfg(test)]
+use crate::currency_amount::amount;
+
+/// How a [`CurrencyFormat`] identifies the currency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CurrencyDisplay {
+    /// The localized symbol: `$1.10`. Falls back to the currency's own
+    /// symbol, then to its code.
+    #[default]
+    Symbol,
+    /// The code: `USD 1.10`.
+    Code,
+    /// The localized name, in the singular or plural as the number requires:
+    /// `1.10 US dollars`. Falls back to the English ISO 4217 name.
+    Name,
+}
+
+/// A locale-aware formatter for [`CurrencyAmount`] values, for display to
+/// people. `Display` for `CurrencyAmount` is unaffected.
+///
+/// ```
+/// use cbor_book::*;
+///
+/// let amount = CurrencyAmount::new(CurrencyCode::new("EUR"), "-1234.56".parse().unwrap());
+/// let format = CurrencyFormat::new(Locale::DeDe);
+/// assert_eq!(format.format(&amount), "-1.234,56\u{a0}€");
+///
+/// let format = CurrencyFormat::new(Locale::EnUs).with_accounting(true);
+/// assert_eq!(format.format(&amount), "(€1,234.56)");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurrencyFormat {
+    locale: Locale,
+    display: CurrencyDisplay,
+    accounting: bool,
+    pad_to_minor_units: bool,
+}
+
+impl CurrencyFormat {
+    /// A format for `locale` that shows the symbol, uses a minus sign for
+    /// negatives, and pads to the currency's minor units.
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            display: CurrencyDisplay::Symbol,
+            accounting: false,
+            pad_to_minor_units: true,
+        }
+    }
+
+    pub fn with_display(mut self, display: CurrencyDisplay) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Uses the locale's accounting pattern for negatives, which is
+    /// parentheses in en-US and fr-CA and a minus sign in de-DE. Amounts
+    /// shown with [`CurrencyDisplay::Name`] always use a minus sign.
+    pub fn with_accounting(mut self, accounting: bool) -> Self {
+        self.accounting = accounting;
+        self
+    }
+
+    /// Pads the fraction with zeros to the currency's minor units, so USD
+    /// 1.1 shows as `$1.10`. On by default. Digits beyond the minor units are
+    /// always shown, never rounded away.
+    pub fn with_minor_unit_padding(mut self, pad: bool) -> Self {
+        self.pad_to_minor_units = pad;
+        self
+    }
+
+    pub fn format(&self, amount: &CurrencyAmount) -> String {
+        let data = self.locale.data();
+        let currency = amount.currency();
+
+        let min_fraction_digits = if self.pad_to_minor_units {
+            currency.minor_units().unwrap_or(0) as usize
+        } else {
+            0
+        };
+        let number = DecimalFormat::new()
+            .with_grouping_separator(data.grouping_separator)
+            .with_decimal_mark(data.decimal_mark)
+            .with_min_fraction_digits(min_fraction_digits)
+            .format(*amount.amount());
+        let (negative, digits) = match number.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, number.as_str()),
+        };
+
+        let body = match self.display {
+            CurrencyDisplay::Symbol => {
+                let symbol = data
+                    .symbol(currency.code())
+                    .map(String::from)
+                    .or_else(|| currency.symbol())
+                    .unwrap_or_else(|| currency.code().to_string());
+                place_currency(data, &symbol, digits)
+            }
+            CurrencyDisplay::Code => place_currency(data, currency.code(), digits),
+            CurrencyDisplay::Name => {
+                let (integer, fraction) = digits.split_once(data.decimal_mark).unwrap_or((digits, ""));
+                let integer: String = integer.chars().filter(|c| *c != data.grouping_separator).collect();
+                let category = data.plural_category(&integer, fraction);
+                let name = data
+                    .name(currency.code(), category)
+                    .map(String::from)
+                    .or_else(|| currency.name())
+                    .unwrap_or_else(|| currency.code().to_string());
+                format!("{} {}", digits, name)
+            }
+        };
+
+        let parentheses = self.accounting
+            && data.accounting_parentheses
+            && self.display != CurrencyDisplay::Name;
+        match (negative, parentheses) {
+            (false, _) => body,
+            (true, true) => format!("({})", body),
+            (true, false) => format!("-{}", body),
+        }
+    }
+}
+
+/// Places the currency according to the locale's pattern. A currency that
+/// ends in a letter is kept apart from the digits with a no-break space, as
+/// CLDR's currency spacing rules do: `USD 1.10` but `$1.10`.
+fn place_currency(data: &LocaleData, currency: &str, digits: &str) -> String {
+    match data.currency_position {
+        CurrencyPosition::Before => {
+            let spacing = if currency.ends_with(char::is_alphabetic) { "\u{a0}" } else { "" };
+            format!("{}{}{}", currency, spacing, digits)
+        }
+        CurrencyPosition::After => format!("{}\u{a0}{}", digits, currency),
+    }
+}
+
+impl CurrencyAmount {
+    /// Formats this amount for display with the given options.
+    pub fn format(&self, format: &CurrencyFormat) -> String {
+        format.format(self)
+    }
+}
+
+#[test]
+fn currency_format_locales() {
+    let [en, fr, de] = Locale::ALL.map(CurrencyFormat::new);
+
+    assert_eq!(en.format(&amount("USD", "1.1")), "$1.10");
+    assert_eq!(fr.format(&amount("CAD", "1.1")), "1,10\u{a0}$");
+    assert_eq!(de.format(&amount("EUR", "1234.56")), "1.234,56\u{a0}€");
+
+    assert_eq!(en.format(&amount("USD", "-1234567.8")), "-$1,234,567.80");
+    assert_eq!(fr.format(&amount("USD", "-1234.5")), "-1\u{a0}234,50\u{a0}$\u{a0}US");
+    assert_eq!(en.format(&amount("CAD", "5")), "CA$5.00");
+    assert_eq!(de.format(&amount("JPY", "1234")), "1.234\u{a0}¥");
+    assert_eq!(en.format(&amount("CHF", "1.5")), "CHF\u{a0}1.50");
+
+    // No localized or ISO symbol: fall back to the code
+    assert_eq!(en.format(&amount("XAU", "2.5")), "XAU\u{a0}2.5");
+    assert_eq!(en.format(&amount("ABC", "2.5")), "ABC\u{a0}2.5");
+
+    // Extra precision is kept, and padding can be turned off
+    assert_eq!(en.format(&amount("USD", "0.125")), "$0.125");
+    let unpadded = en.clone().with_minor_unit_padding(false);
+    assert_eq!(unpadded.format(&amount("USD", "1.1")), "$1.1");
+}
+
+#[test]
+fn currency_format_display_and_accounting() {
+    let [en, fr, de] = Locale::ALL.map(CurrencyFormat::new);
+
+    let code = |f: &CurrencyFormat| f.clone().with_display(CurrencyDisplay::Code);
+    assert_eq!(code(&en).format(&amount("USD", "1.1")), "USD\u{a0}1.10");
+    assert_eq!(code(&fr).format(&amount("USD", "1.1")), "1,10\u{a0}USD");
+    assert_eq!(code(&de).format(&amount("USD", "-1.1")), "-1,10\u{a0}USD");
+    assert_eq!(code(&en).format(&amount("BHD", "1.5")), "BHD\u{a0}1.500");
+
+    let name = |f: &CurrencyFormat| f.clone().with_display(CurrencyDisplay::Name);
+    assert_eq!(name(&en).format(&amount("USD", "1.1")), "1.10 US dollars");
+    assert_eq!(name(&en).format(&amount("JPY", "1")), "1 Japanese yen");
+    assert_eq!(name(&en).format(&amount("EUR", "1")), "1.00 euros");
+    assert_eq!(name(&en).with_minor_unit_padding(false).format(&amount("EUR", "1")), "1 euro");
+    assert_eq!(name(&fr).format(&amount("CAD", "1.5")), "1,50 dollar canadien");
+    assert_eq!(name(&fr).format(&amount("CAD", "2")), "2,00 dollars canadiens");
+    assert_eq!(name(&de).format(&amount("GBP", "1000")), "1.000,00 Britische Pfund");
+    assert_eq!(name(&de).format(&amount("CHF", "3")), "3,00 Schweizer Franken");
+    assert_eq!(name(&en).format(&amount("SEK", "3")), "3.00 Swedish Krona");
+
+    let accounting = |f: &CurrencyFormat| f.clone().with_accounting(true);
+    assert_eq!(accounting(&en).format(&amount("USD", "-1.1")), "($1.10)");
+    assert_eq!(accounting(&en).format(&amount("USD", "1.1")), "$1.10");
+    assert_eq!(accounting(&fr).format(&amount("CAD", "-1.1")), "(1,10\u{a0}$)");
+    assert_eq!(accounting(&de).format(&amount("EUR", "-1.1")), "-1,10\u{a0}€");
+    assert_eq!(accounting(&name(&en)).format(&amount("USD", "-2")), "-2.00 US dollars");
+
+    // Display is unchanged
+    assert_eq!(amount("USD", "1.1").to_string(), "USD 1.1");
+}
@@ -0,0 +1,307 @@
+use crate::{
+    CurrencyAmount,
+    CurrencyCode,
+    DecimalFraction,
+    Error,
+    Locale,
+    Result,
+    iso4217::ISO_4217,
+    locale::LocaleData,
+};
+
+impl std::str::FromStr for CurrencyAmount {
+    type Err = Error;
+
+    /// Parses the output of `Display` (`"USD 1.10"`) and similar forms: the
+    /// code may come before or after the number (`"1.10 USD"`), negatives
+    /// may use a minus sign or parentheses (`"(12.00) EUR"`), and a symbol
+    /// may stand in for the code if only one currency uses it (`"€5"`). The
+    /// number uses `.` as the decimal mark, without grouping.
+    ///
+    /// The digits written become the mantissa, as with [`DecimalFraction`],
+    /// so `"USD 1.10"` parses exactly as `4([-2, 110])`.
+    fn from_str(s: &str) -> Result<Self> {
+        parse_currency_amount(s, None)
+    }
+}
+
+impl CurrencyAmount {
+    /// Parses a money string as written in `locale`: `"$1,234.56"` in en-US,
+    /// `"1 234,56 $"` in fr-CA, `"1.234,56 €"` in de-DE. Accepts the same
+    /// forms as `FromStr`, plus the locale's grouping and decimal mark,
+    /// localized symbols and names (`"$ US"`, `"2,00 dollars canadiens"`),
+    /// and symbols like `$` that are ambiguous without a locale, which
+    /// resolve to the locale's [default
+    /// currency](Locale::default_currency) where it uses them.
+    pub fn parse_localized(s: &str, locale: Locale) -> Result<Self> {
+        parse_currency_amount(s, Some(locale))
+    }
+}
+
+/// Strips sign markers and whitespace from both ends of `text`, counting
+/// what was removed, and returns what is left.
+fn strip_markers<'a>(text: &'a str, markers: &[char], counts: &mut [usize]) -> &'a str {
+    let is_marker = |c: char| c.is_whitespace() || markers.contains(&c);
+    let rest = text.trim_start_matches(is_marker);
+    let stripped = &text[..text.len() - rest.len()];
+    let trimmed = rest.trim_end_matches(is_marker);
+    let stripped = stripped.chars().chain(rest[trimmed.len()..].chars());
+    for c in stripped {
+        if let Some(i) = markers.iter().position(|m| *m == c) {
+            counts[i] += 1;
+        }
+    }
+    trimmed
+}
+
+fn parse_currency_amount(s: &str, locale: Option<Locale>) -> Result<CurrencyAmount> {
+    let invalid = |reason: &str| Error::InvalidCurrencyAmount {
+        input: s.to_string(),
+        reason: reason.to_string(),
+    };
+    let data = locale.map(Locale::data);
+
+    // Registered codes may contain digits, so a known code in the first or
+    // last word is taken out before looking for the number.
+    let (known, text) = match split_code_with_digits(s) {
+        Some((code, text)) => (Some(code), text),
+        None => (None, s.to_string()),
+    };
+
+    // The number runs from the first digit to the last; the currency and
+    // any sign markers are on either side.
+    let first = text.find(|c: char| c.is_ascii_digit()).ok_or_else(|| invalid("no digits"))?;
+    let last = text.rfind(|c: char| c.is_ascii_digit()).unwrap_or(first) + 1;
+    let (prefix, number, suffix) = (&text[..first], &text[first..last], &text[last..]);
+
+    // [minus, plus, open, close]
+    let mut counts = [0; 4];
+    let markers = ['-', '+', '(', ')'];
+    let before = strip_markers(prefix, &markers, &mut counts);
+    let after = strip_markers(suffix, &markers, &mut counts);
+    let [minus, plus, open, close] = counts;
+    if open != close || open > 1 || minus + plus + open > 1 {
+        return Err(invalid("malformed sign"));
+    }
+    let negative = minus == 1 || open == 1;
+
+    let currency = match (known, before.is_empty(), after.is_empty()) {
+        (Some(code), true, true) => Ok(code),
+        (None, true, true) => return Err(invalid("no currency")),
+        (Some(_), _, _) | (None, false, false) => return Err(invalid("currency on both sides of the number")),
+        (None, false, true) => resolve_currency(before, data),
+        (None, true, false) => resolve_currency(after, data),
+    };
+    let currency = currency.map_err(|error| match error {
+        Error::InvalidCurrencyCode(_) => invalid("unknown currency"),
+        error => error,
+    })?;
+
+    let number = match data {
+        // Plain notation only, as in parse_localized: no exponents.
+        None if number.bytes().all(|b| b.is_ascii_digit() || b == b'.') => number.to_string(),
+        None => return Err(invalid("malformed number")),
+        Some(data) => delocalize_number(number, data).ok_or_else(|| invalid("malformed number"))?,
+    };
+    let sign = if negative { "-" } else { "" };
+    let amount: DecimalFraction = format!("{}{}", sign, number)
+        .parse()
+        .map_err(|error| match error {
+            Error::InvalidDecimalFraction { .. } => invalid("malformed number"),
+            error => error,
+        })?;
+
+    Ok(CurrencyAmount::new(currency, amount))
+}
+
+/// Finds a known currency code containing digits, such as a registered
+/// `"PTS2"`, as the first or last whitespace-separated word of `s` (ignoring
+/// sign markers), and returns it along with `s` without it.
+fn split_code_with_digits(s: &str) -> Option<(CurrencyCode, String)> {
+    let is_marker = |c: char| matches!(c, '-' | '+' | '(' | ')');
+    let words = [s.split_whitespace().next()?, s.split_whitespace().next_back()?];
+    words.into_iter().find_map(|word| {
+        let word = word.trim_matches(is_marker);
+        let candidate = !word.is_empty()
+            && word.bytes().any(|b| b.is_ascii_digit())
+            && word.bytes().all(|b| b.is_ascii_alphanumeric());
+        if !candidate {
+            return None;
+        }
+        let code = known_code(word).ok()?;
+        // `word` is a subslice of `s`, so its offset locates it.
+        let start = word.as_ptr() as usize - s.as_ptr() as usize;
+        Some((code, format!("{}{}", &s[..start], &s[start + word.len()..])))
+    })
+}
+
+/// Looks up a code, in any case, among the ISO 4217 and registered codes,
+/// whatever its validity dates: amounts in withdrawn currencies like
+/// `"DEM 4.99"` are still meaningful.
+fn known_code(text: &str) -> Result<CurrencyCode> {
+    let code = text.to_ascii_uppercase();
+    CurrencyCode::known_definition(&code)?;
+    Ok(CurrencyCode::new(&code))
+}
+
+/// Rewrites a number written with the locale's grouping and decimal mark in
+/// plain notation, or returns `None` if the grouping is malformed. Where the
+/// grouping separator is a space, any kind of space is accepted.
+fn delocalize_number(number: &str, data: &LocaleData) -> Option<String> {
+    let is_separator = |c: char| {
+        c == data.grouping_separator || (data.grouping_separator.is_whitespace() && c.is_whitespace())
+    };
+    let (integer, fraction) = number.split_once(data.decimal_mark).unwrap_or((number, ""));
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let groups: Vec<&str> = integer.split(is_separator).collect();
+    let well_formed = groups.iter().all(|g| !g.is_empty() && g.bytes().all(|b| b.is_ascii_digit()))
+        && (groups.len() == 1 || (groups[0].len() <= 3 && groups[1..].iter().all(|g| g.len() == 3)));
+    if !well_formed {
+        return None;
+    }
+
+    let integer = groups.concat();
+    if fraction.is_empty() && !number.ends_with(data.decimal_mark) {
+        Some(integer)
+    } else {
+        Some(format!("{}.{}", integer, fraction))
+    }
+}
+
+/// Resolves a currency code, symbol, or (with a locale) localized name.
+/// Text that could be a code is looked up as one first, then as a symbol
+/// like `"RM"`.
+fn resolve_currency(text: &str, data: Option<&LocaleData>) -> Result<CurrencyCode> {
+    let code_error = if text.chars().all(|c| c.is_ascii_alphanumeric()) {
+        match known_code(text) {
+            Ok(code) => return Ok(code),
+            Err(error) => Some(error),
+        }
+    } else {
+        None
+    };
+
+    // Localized spellings may use any kind of space.
+    let same = |a: &str, b: &str| {
+        a.split_whitespace().eq(b.split_whitespace())
+            || a.to_lowercase().split_whitespace().eq(b.to_lowercase().split_whitespace())
+    };
+    if let Some(data) = data {
+        let symbol = data.symbols.iter().find(|(_, symbol)| same(symbol, text));
+        let name = data.language
+            .currency_names()
+            .iter()
+            .find(|names| same(names.one, text) || same(names.other, text));
+        if let Some(code) = symbol.map(|(code, _)| code).or(name.map(|names| &names.code)) {
+            return Ok(CurrencyCode::new(code));
+        }
+    }
+
+    let candidates: Vec<&str> = ISO_4217
+        .iter()
+        .filter(|entry| entry.valid_until.is_none() && entry.symbol == Some(text))
+        .map(|entry| entry.code)
+        .collect();
+    match candidates.as_slice() {
+        [] => Err(code_error.unwrap_or_else(|| Error::InvalidCurrencyCode(text.to_string()))),
+        [code] => Ok(CurrencyCode::new(code)),
+        codes => match data {
+            Some(data) if codes.contains(&data.default_currency) => {
+                Ok(CurrencyCode::new(data.default_currency))
+            }
+            _ => Err(Error::AmbiguousCurrencySymbol(text.to_string())),
+        },
+    }
+}
+
+#[test]
+fn currency_amount_from_str() {
+    let parse = |s: &str| s.parse::<CurrencyAmount>();
+    let usd = |exponent, mantissa| CurrencyAmount::new(CurrencyCode::new("USD"), DecimalFraction::new(exponent, mantissa));
+
+    for s in ["USD 1.10", "1.10 USD", "usd1.10", " USD\u{a0}1.10 "] {
+        assert_eq!(parse(s).unwrap(), usd(-2, 110), "{s}");
+    }
+    for s in ["USD -1.10", "-1.10 USD", "-USD 1.10", "(1.10) USD", "USD (1.10)", "(USD 1.10)"] {
+        assert_eq!(parse(s).unwrap(), usd(-2, -110), "{s}");
+    }
+    assert_eq!(parse("(12.00) EUR").unwrap().to_string(), "EUR -12.00");
+    assert_eq!(parse("€5").unwrap().to_string(), "EUR 5");
+    assert_eq!(parse("RM 5").unwrap().to_string(), "MYR 5");
+    assert_eq!(parse("1500 Ft").unwrap().to_string(), "HUF 1500");
+    assert_eq!(parse("Rs 10"), Err(Error::AmbiguousCurrencySymbol("Rs".into())));
+    assert_eq!(parse("+7 JPY").unwrap().to_string(), "JPY 7");
+
+    // Withdrawn currencies still parse
+    assert_eq!(parse("DEM 4.99").unwrap().to_string(), "DEM 4.99");
+    assert_eq!(parse("(1500 frf)").unwrap().to_string(), "FRF -1500");
+
+    // Round-trips Display exactly
+    for amount in [usd(-2, 110), usd(0, 0), usd(-3, -5), usd(0, i64::MIN)] {
+        assert_eq!(parse(&amount.to_string()).unwrap(), amount);
+    }
+
+    assert_eq!(parse("$1.10"), Err(Error::AmbiguousCurrencySymbol("$".into())));
+    assert_eq!(parse("£1.10"), Err(Error::AmbiguousCurrencySymbol("£".into())));
+    let reason = |s: &str| match parse(s) {
+        Err(Error::InvalidCurrencyAmount { reason, .. }) => reason,
+        other => panic!("{s}: {other:?}"),
+    };
+    assert_eq!(reason("1.10"), "no currency");
+    assert_eq!(reason("USD"), "no digits");
+    assert_eq!(reason("USD 1.10 EUR"), "currency on both sides of the number");
+    assert_eq!(reason("-(1.10) USD"), "malformed sign");
+    assert_eq!(reason("(1.10 USD"), "malformed sign");
+    assert_eq!(reason("1,234.56 USD"), "malformed number");
+    assert_eq!(reason("1e5 USD"), "malformed number");
+    assert_eq!(reason("USD 1.5E+2"), "malformed number");
+    assert_eq!(reason("1.10 ¤"), "unknown currency");
+    assert_eq!(parse("1.10 ABC"), Err(Error::UnknownCurrencyCode("ABC".into())));
+    assert_eq!(parse("1.10 DOLLARS"), Err(Error::UnknownCurrencyCode("DOLLARS".into())));
+}
+
+#[test]
+fn currency_amount_parse_localized() {
+    let parse = |s: &str, locale| CurrencyAmount::parse_localized(s, locale).map(|a| a.to_string());
+
+    assert_eq!(parse("$1.10", Locale::EnUs).unwrap(), "USD 1.10");
+    assert_eq!(parse("-$1,234,567.80", Locale::EnUs).unwrap(), "USD -1234567.80");
+    assert_eq!(parse("($1.10)", Locale::EnUs).unwrap(), "USD -1.10");
+    assert_eq!(parse("CA$5.00", Locale::EnUs).unwrap(), "CAD 5.00");
+    assert_eq!(parse("1.10 US dollars", Locale::EnUs).unwrap(), "USD 1.10");
+
+    assert_eq!(parse("1,10 $", Locale::FrCa).unwrap(), "CAD 1.10");
+    assert_eq!(parse("1 234,50 $ US", Locale::FrCa).unwrap(), "USD 1234.50");
+    assert_eq!(parse("-1\u{a0}234,50\u{a0}$", Locale::FrCa).unwrap(), "CAD -1234.50");
+    assert_eq!(parse("2,00 dollars canadiens", Locale::FrCa).unwrap(), "CAD 2.00");
+
+    assert_eq!(parse("1.234,56 €", Locale::DeDe).unwrap(), "EUR 1234.56");
+    assert_eq!(parse("(12,00) EUR", Locale::DeDe).unwrap(), "EUR -12.00");
+    assert_eq!(parse("1.234 USD", Locale::DeDe).unwrap(), "USD 1234");
+    assert_eq!(parse("5 £", Locale::DeDe).unwrap(), "GBP 5");
+
+    // Symbols that are ambiguous on their own resolve through the locale
+    assert_eq!(parse("$1,10", Locale::DeDe).unwrap(), "USD 1.10");
+    assert_eq!(parse("1,00 ¥", Locale::FrCa).unwrap(), "JPY 1.00");
+
+    // Grouping must be well formed, and only in the integer part
+    for s in ["1.234,56 $", "12,34,567.00 $", "1,2345.00 $", "1.234.5 $"] {
+        assert!(
+            matches!(CurrencyAmount::parse_localized(s, Locale::EnUs), Err(Error::InvalidCurrencyAmount { .. })),
+            "{s}"
+        );
+    }
+
+    // Every formatted amount parses back exactly
+    let formats = Locale::ALL.map(|locale| (locale, crate::CurrencyFormat::new(locale)));
+    for s in ["USD -1234567.80", "EUR 0.00", "CAD 1.10", "JPY 1234", "GBP 999.99", "CHF 1.50"] {
+        let amount: CurrencyAmount = s.parse().unwrap();
+        for (locale, format) in &formats {
+            let formatted = format.format(&amount);
+            assert_eq!(CurrencyAmount::parse_localized(&formatted, *locale).unwrap(), amount, "{formatted}");
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{ DecimalFraction, RoundingMode };
+
+/// How a [`DecimalFormat`] lays out the digits of a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// Plain positional notation: `1234.5`.
+    #[default]
+    Positional,
+    /// One nonzero digit before the decimal mark: `1.2345e3`.
+    Scientific,
+    /// An exponent that is a multiple of three: `1.2345e3`, `12.345e3`.
+    Engineering,
+}
+
+/// A configurable formatter for [`DecimalFraction`] values.
+///
+/// The default format produces the same output as `Display`. Options are set
+/// with the `with_` methods:
+///
+/// ```
+/// use cbor_book::*;
+///
+/// let format = DecimalFormat::new()
+///     .with_grouping_separator(',')
+///     .with_min_fraction_digits(2);
+/// assert_eq!(format.format(DecimalFraction::new(0, 1234567)), "1,234,567.00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecimalFormat {
+    min_fraction_digits: usize,
+    max_fraction_digits: Option<usize>,
+    max_significant_digits: Option<usize>,
+    grouping_separator: Option<char>,
+    grouping_size: usize,
+    decimal_mark: char,
+    notation: Notation,
+    plus_sign: bool,
+    rounding_mode: RoundingMode,
+}
+
+impl Default for DecimalFormat {
+    fn default() -> Self {
+        Self {
+            min_fraction_digits: 0,
+            max_fraction_digits: None,
+            max_significant_digits: None,
+            grouping_separator: None,
+            grouping_size: 3,
+            decimal_mark: '.',
+            notation: Notation::Positional,
+            plus_sign: false,
+            rounding_mode: RoundingMode::HalfEven,
+        }
+    }
+}
+
+impl DecimalFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pads the fraction with zeros to at least this many digits.
+    pub fn with_min_fraction_digits(mut self, digits: usize) -> Self {
+        self.min_fraction_digits = digits;
+        self
+    }
+
+    /// Rounds the fraction to at most this many digits. Takes precedence over
+    /// the minimum if the two conflict.
+    pub fn with_max_fraction_digits(mut self, digits: usize) -> Self {
+        self.max_fraction_digits = Some(digits);
+        self
+    }
+
+    /// Sets both the minimum and maximum number of fraction digits.
+    pub fn with_fraction_digits(self, digits: usize) -> Self {
+        self.with_min_fraction_digits(digits).with_max_fraction_digits(digits)
+    }
+
+    /// Rounds to at most this many significant digits (at least one).
+    pub fn with_max_significant_digits(mut self, digits: usize) -> Self {
+        self.max_significant_digits = Some(digits.max(1));
+        self
+    }
+
+    /// Separates groups of integer digits with `separator`.
+    pub fn with_grouping_separator(mut self, separator: char) -> Self {
+        self.grouping_separator = Some(separator);
+        self
+    }
+
+    /// Sets the number of digits per group (at least one; default three).
+    pub fn with_grouping_size(mut self, size: usize) -> Self {
+        self.grouping_size = size.max(1);
+        self
+    }
+
+    /// Sets the character placed between the integer and fraction digits.
+    pub fn with_decimal_mark(mut self, mark: char) -> Self {
+        self.decimal_mark = mark;
+        self
+    }
+
+    /// Sets the notation. Defaults to [`Notation::Positional`].
+    pub fn with_notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Prefixes non-negative values with `+`.
+    pub fn with_plus_sign(mut self, plus_sign: bool) -> Self {
+        self.plus_sign = plus_sign;
+        self
+    }
+
+    /// Sets how digits dropped by the fraction or significant-digit limits
+    /// are rounded. Defaults to [`RoundingMode::HalfEven`].
+    pub fn with_rounding_mode(mut self, mode: RoundingMode) -> Self {
+        self.rounding_mode = mode;
+        self
+    }
+
+    /// Formats `value`. Never fails: rounding is performed at a width that
+    /// can't overflow.
+    pub fn format(&self, value: DecimalFraction) -> String {
+        let mut mantissa = value.mantissa as i128;
+        let mut exponent = value.exponent as i32;
+
+        if let Some(max) = self.max_significant_digits {
+            let digits = digit_count(mantissa) as i32;
+            let max = i32::try_from(max).unwrap_or(i32::MAX);
+            if digits > max {
+                (mantissa, exponent) = self.round_to(mantissa, exponent, exponent + digits - max);
+            }
+        }
+
+        // The exponent shown in scientific or engineering notation. Rounding
+        // can carry into a new leading digit, so repeat until it settles.
+        let mut shown_exponent = 0;
+        loop {
+            if self.notation != Notation::Positional && mantissa != 0 {
+                let lead = digit_count(mantissa) as i32 - 1 + exponent;
+                shown_exponent = match self.notation {
+                    Notation::Engineering => lead.div_euclid(3) * 3,
+                    _ => lead,
+                };
+            }
+            let max = self.max_fraction_digits.map(|max| i32::try_from(max).unwrap_or(i32::MAX));
+            match max {
+                Some(max) if exponent - shown_exponent < -max => {
+                    (mantissa, exponent) = self.round_to(mantissa, exponent, shown_exponent - max);
+                }
+                _ => break,
+            }
+        }
+
+        let mut result = String::new();
+        if mantissa < 0 {
+            result.push('-');
+        } else if self.plus_sign {
+            result.push('+');
+        }
+
+        let (integer, fraction) = split_digits(mantissa.unsigned_abs(), exponent - shown_exponent);
+        self.push_integer(&mut result, &integer);
+
+        let min = self.min_fraction_digits.min(self.max_fraction_digits.unwrap_or(usize::MAX));
+        if !fraction.is_empty() || min > 0 {
+            result.push(self.decimal_mark);
+            result.push_str(&fraction);
+            for _ in fraction.len()..min {
+                result.push('0');
+            }
+        }
+
+        if self.notation != Notation::Positional {
+            result.push_str(&format!("e{}", shown_exponent));
+        }
+        result
+    }
+
+    /// Rounds `mantissa * 10^exponent` to a multiple of `10^target`.
+    fn round_to(&self, mantissa: i128, exponent: i32, target: i32) -> (i128, i32) {
+        let shift = (target - exponent) as u32;
+        (self.rounding_mode.div_pow10(mantissa, shift), target)
+    }
+
+    fn push_integer(&self, result: &mut String, integer: &str) {
+        match self.grouping_separator {
+            None => result.push_str(integer),
+            Some(separator) => {
+                for (i, c) in integer.chars().enumerate() {
+                    if i > 0 && (integer.len() - i).is_multiple_of(self.grouping_size) {
+                        result.push(separator);
+                    }
+                    result.push(c);
+                }
+            }
+        }
+    }
+}
+
+impl DecimalFraction {
+    /// Formats this value with the given options.
+    pub fn format(&self, format: &DecimalFormat) -> String {
+        format.format(*self)
+    }
+}
+
+fn digit_count(mantissa: i128) -> u32 {
+    match mantissa.unsigned_abs() {
+        0 => 1,
+        n => n.ilog10() + 1,
+    }
+}
+
+/// Splits `magnitude * 10^exponent` into integer and fraction digits.
+fn split_digits(magnitude: u128, exponent: i32) -> (String, String) {
+    let digits = magnitude.to_string();
+    if exponent >= 0 {
+        let integer = if magnitude == 0 {
+            digits
+        } else {
+            digits + &"0".repeat(exponent as usize)
+        };
+        return (integer, String::new());
+    }
+
+    let fraction_len = exponent.unsigned_abs() as usize;
+    let digits = if digits.len() <= fraction_len {
+        "0".repeat(fraction_len + 1 - digits.len()) + &digits
+    } else {
+        digits
+    };
+    let (integer, fraction) = digits.split_at(digits.len() - fraction_len);
+    (integer.to_string(), fraction.to_string())
+}
+
+#[test]
+fn decimal_format_default_matches_display() {
+    let format = DecimalFormat::new();
+    for value in [
+        DecimalFraction::new(0, 0),
+        DecimalFraction::new(-1, 11),
+        DecimalFraction::new(-3, -5),
+        DecimalFraction::new(3, 123),
+        DecimalFraction::new(-9, 123456789),
+        DecimalFraction::new(-128, 7),
+    ] {
+        assert_eq!(format.format(value), value.to_string());
+    }
+}
+
+#[test]
+fn decimal_format_positional() {
+    let value = DecimalFraction::new(-3, -1234567891); // -1234567.891
+
+    let format = DecimalFormat::new().with_grouping_separator(',');
+    assert_eq!(format.format(value), "-1,234,567.891");
+
+    let format = DecimalFormat::new()
+        .with_grouping_separator('.')
+        .with_decimal_mark(',')
+        .with_fraction_digits(2);
+    assert_eq!(format.format(value), "-1.234.567,89");
+
+    let format = DecimalFormat::new().with_grouping_separator(' ').with_grouping_size(4);
+    assert_eq!(format.format(DecimalFraction::new(0, 123456789)), "1 2345 6789");
+
+    let format = DecimalFormat::new().with_min_fraction_digits(2);
+    assert_eq!(format.format(DecimalFraction::new(-1, 11)), "1.10");
+    assert_eq!(format.format(DecimalFraction::new(-3, 1105)), "1.105");
+    assert_eq!(format.format(DecimalFraction::new(0, 0)), "0.00");
+
+    let format = DecimalFormat::new().with_max_fraction_digits(2);
+    assert_eq!(format.format(DecimalFraction::new(-3, 1105)), "1.10");
+    assert_eq!(format.format(DecimalFraction::new(-3, 1115)), "1.12");
+    assert_eq!(format.format(DecimalFraction::new(-3, -1)), "0.00");
+    let format = format.with_rounding_mode(RoundingMode::HalfUp);
+    assert_eq!(format.format(DecimalFraction::new(-3, 1105)), "1.11");
+
+    let format = DecimalFormat::new().with_max_significant_digits(3);
+    assert_eq!(format.format(DecimalFraction::new(-3, 1234567)), "1230");
+    assert_eq!(format.format(DecimalFraction::new(-6, 1234567)), "1.23");
+    assert_eq!(format.format(DecimalFraction::new(-2, 99999)), "1000");
+
+    let format = DecimalFormat::new().with_plus_sign(true);
+    assert_eq!(format.format(DecimalFraction::new(-1, 11)), "+1.1");
+    assert_eq!(format.format(DecimalFraction::new(-1, -11)), "-1.1");
+    assert_eq!(format.format(DecimalFraction::new(0, 0)), "+0");
+}
+
+#[test]
+fn decimal_format_exponential() {
+    let format = DecimalFormat::new().with_notation(Notation::Scientific);
+    assert_eq!(format.format(DecimalFraction::new(0, 12345)), "1.2345e4");
+    assert_eq!(format.format(DecimalFraction::new(-9, 123)), "1.23e-7");
+    assert_eq!(format.format(DecimalFraction::new(3, -4)), "-4e3");
+    assert_eq!(format.format(DecimalFraction::new(0, 0)), "0e0");
+    assert_eq!(format.format(DecimalFraction::new(-2, 110)), "1.10e0");
+
+    let format = format.with_max_fraction_digits(2);
+    assert_eq!(format.format(DecimalFraction::new(0, 12345)), "1.23e4");
+    assert_eq!(format.format(DecimalFraction::new(0, 99999)), "1.00e5");
+
+    let format = DecimalFormat::new().with_notation(Notation::Engineering);
+    assert_eq!(format.format(DecimalFraction::new(0, 12345)), "12.345e3");
+    assert_eq!(format.format(DecimalFraction::new(-9, 123)), "123e-9");
+    assert_eq!(format.format(DecimalFraction::new(-10, 123)), "12.3e-9");
+    assert_eq!(format.format(DecimalFraction::new(127, 1)), "10e126");
+
+    let format = format.with_max_significant_digits(2).with_plus_sign(true);
+    assert_eq!(format.format(DecimalFraction::new(0, 12345)), "+12e3");
+    assert_eq!(format.format(DecimalFraction::new(0, 99999)), "+100e3");
+}
+
+#[test]
+fn decimal_format_extremes() {
+    let min = DecimalFraction::new(0, i64::MIN);
+    assert_eq!(min.to_string(), "-9223372036854775808");
+    assert_eq!(
+        DecimalFormat::new().with_grouping_separator(',').format(min),
+        "-9,223,372,036,854,775,808"
+    );
+    assert_eq!(
+        DecimalFormat::new().with_notation(Notation::Scientific).with_max_significant_digits(3).format(min),
+        "-9.22e18"
+    );
+    assert_eq!(DecimalFraction::new(-2, i64::MIN).to_string(), "-92233720368547758.08");
+
+    let format = DecimalFormat::new().with_max_fraction_digits(0);
+    assert_eq!(format.format(DecimalFraction::new(-128, i64::MAX)), "0");
+    let format = format.with_rounding_mode(RoundingMode::Ceiling);
+    assert_eq!(format.format(DecimalFraction::new(-128, 1)), "1");
+    let format = DecimalFormat::new().with_max_significant_digits(1);
+    assert_eq!(format.format(DecimalFraction::new(127, i64::MAX)), format!("9{}", "0".repeat(145)));
+}
+
+#[test]
+fn decimal_format_unbounded_limits() {
+    let format = DecimalFormat::new().with_max_significant_digits(usize::MAX);
+    assert_eq!(format.format(DecimalFraction::new(0, 123)), "123");
+    let format = DecimalFormat::new().with_max_fraction_digits(usize::MAX);
+    assert_eq!(format.format(DecimalFraction::new(-2, 123)), "1.23");
+    let format = format.with_notation(Notation::Scientific);
+    assert_eq!(format.format(DecimalFraction::new(-2, 123)), "1.23e0");
+}
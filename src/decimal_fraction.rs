@@ -1,8 +1,10 @@
+use std::{ cmp, hash::{ Hash, Hasher } };
+
 use dcbor::prelude::*;
 
-use crate::tags::TAG_DECIMAL_FRACTION;
+use crate::{ BigFloat, Error, Result, RoundingMode, tags::TAG_DECIMAL_FRACTION };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 // ANCHOR: example_6
 pub struct DecimalFraction {
     pub exponent: i8,
@@ -22,13 +24,165 @@ impl DecimalFraction {
     }
 }
 
+impl DecimalFraction {
+    /// Returns the canonical form of this value: trailing zeros of the
+    /// mantissa are moved into the exponent, and zero is always `0e0`.
+    pub fn normalize(self) -> Self {
+        if self.mantissa == 0 {
+            return Self::new(0, 0);
+        }
+        let (mut exponent, mut mantissa) = (self.exponent, self.mantissa);
+        while mantissa % 10 == 0 && exponent < i8::MAX {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        Self::new(exponent, mantissa)
+    }
+
+    /// Returns `true` if this value is already in canonical form.
+    pub fn is_normalized(self) -> bool {
+        self == self.normalize()
+    }
+
+    /// Encodes the canonical form returned by [`DecimalFraction::normalize`].
+    ///
+    /// Two decimal fractions with the same numeric value, like `4([-1, 10])`
+    /// and `4([0, 1])`, otherwise encode to different bytes and therefore
+    /// produce different digests. In the spirit of dCBOR's numeric reduction
+    /// rules, this always emits one form per value. `to_cbor()` encodes the
+    /// value exactly as given.
+    pub fn to_canonical_cbor(self) -> CBOR {
+        self.normalize().to_cbor()
+    }
+
+    /// Decodes like `TryFrom<CBOR>`, but rejects values that are not in
+    /// canonical form.
+    pub fn try_from_canonical(cbor: CBOR) -> Result<Self, dcbor::Error> {
+        let value = Self::try_from(cbor)?;
+        if !value.is_normalized() {
+            return Err(Error::NonCanonicalDecimalFraction(value.to_string()).into());
+        }
+        Ok(value)
+    }
+}
+
+/// Returns `10^n` if it fits in an `i64`.
+pub(crate) fn pow10(n: u32) -> Option<i64> {
+    10i64.checked_pow(n)
+}
+
+/// Multiplies `mantissa` by `10^n`, reporting overflow.
+pub(crate) fn scale_mantissa(mantissa: i64, n: u32) -> Result<i64> {
+    if mantissa == 0 {
+        return Ok(0);
+    }
+    pow10(n)
+        .and_then(|p| mantissa.checked_mul(p))
+        .ok_or(Error::MantissaOverflow)
+}
+
+impl DecimalFraction {
+    /// Rewrites `self` and `other` with a common exponent, the smaller of the
+    /// two, so their mantissas can be combined directly.
+    fn aligned(self, other: Self) -> Result<(i8, i64, i64)> {
+        let exponent = self.exponent.min(other.exponent);
+        let a = scale_mantissa(self.mantissa, (self.exponent as i16 - exponent as i16) as u32)?;
+        let b = scale_mantissa(other.mantissa, (other.exponent as i16 - exponent as i16) as u32)?;
+        Ok((exponent, a, b))
+    }
+
+    /// Exact addition. The result carries the smaller of the two exponents.
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        let (exponent, a, b) = self.aligned(other)?;
+        let mantissa = a.checked_add(b).ok_or(Error::MantissaOverflow)?;
+        Ok(Self::new(exponent, mantissa))
+    }
+
+    /// Exact subtraction. The result carries the smaller of the two exponents.
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        let (exponent, a, b) = self.aligned(other)?;
+        let mantissa = a.checked_sub(b).ok_or(Error::MantissaOverflow)?;
+        Ok(Self::new(exponent, mantissa))
+    }
+
+    /// Exact multiplication. The result exponent is the sum of the exponents.
+    pub fn checked_mul(self, other: Self) -> Result<Self> {
+        let exponent = self.exponent
+            .checked_add(other.exponent)
+            .ok_or(Error::ExponentOverflow)?;
+        let mantissa = self.mantissa
+            .checked_mul(other.mantissa)
+            .ok_or(Error::MantissaOverflow)?;
+        Ok(Self::new(exponent, mantissa))
+    }
+
+    /// Negation. Fails only for a mantissa of `i64::MIN`.
+    pub fn checked_neg(self) -> Result<Self> {
+        let mantissa = self.mantissa.checked_neg().ok_or(Error::MantissaOverflow)?;
+        Ok(Self::new(self.exponent, mantissa))
+    }
+
+    /// Divides `self` by `divisor`, producing a result with exactly `scale`
+    /// digits after the decimal point (i.e. an exponent of `-scale`), rounded
+    /// with `mode`. A negative `scale` rounds to tens, hundreds, etc.
+    pub fn div_with(self, divisor: Self, scale: i8, mode: RoundingMode) -> Result<Self> {
+        if divisor.mantissa == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let exponent = scale.checked_neg().ok_or(Error::ExponentOverflow)?;
+
+        // self / divisor = (m1 / m2) * 10^(e1 - e2), and we want the
+        // quotient in units of 10^exponent, so shift by k = e1 - e2 - exponent.
+        let k = self.exponent as i32 - divisor.exponent as i32 - exponent as i32;
+        let quotient = if k >= 0 {
+            let n = if self.mantissa == 0 {
+                0
+            } else {
+                10i128
+                    .checked_pow(k as u32)
+                    .and_then(|p| (self.mantissa as i128).checked_mul(p))
+                    .ok_or(Error::MantissaOverflow)?
+            };
+            mode.div(n, divisor.mantissa as i128)
+        } else {
+            mode.div_scaled(self.mantissa as i128, divisor.mantissa as i128, k.unsigned_abs())
+        };
+
+        let mantissa = i64::try_from(quotient).map_err(|_| Error::MantissaOverflow)?;
+        Ok(Self::new(exponent, mantissa))
+    }
+
+    /// Returns the same value expressed with the given `exponent`, rounding
+    /// with `mode` if digits must be dropped. Lowering the exponent is exact
+    /// but fails if the mantissa overflows.
+    pub fn quantize(self, exponent: i8, mode: RoundingMode) -> Result<Self> {
+        if exponent <= self.exponent {
+            let shift = (self.exponent as i16 - exponent as i16) as u32;
+            return Ok(Self::new(exponent, scale_mantissa(self.mantissa, shift)?));
+        }
+
+        let shift = (exponent as i16 - self.exponent as i16) as u32;
+        let mantissa = i64::try_from(mode.div_pow10(self.mantissa as i128, shift))
+            .map_err(|_| Error::MantissaOverflow)?;
+        Ok(Self::new(exponent, mantissa))
+    }
+
+    /// Returns the same value with exactly `scale` digits after the decimal
+    /// point, rounding with `mode` if necessary. Equivalent to
+    /// `quantize(-scale, mode)`.
+    pub fn rescale(self, scale: i8, mode: RoundingMode) -> Result<Self> {
+        let exponent = scale.checked_neg().ok_or(Error::ExponentOverflow)?;
+        self.quantize(exponent, mode)
+    }
+}
+
 impl std::fmt::Display for DecimalFraction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.mantissa == 0 {
             return write!(f, "0");
         }
 
-        let abs_value = self.mantissa.abs();
+        let abs_value = self.mantissa.unsigned_abs();
         let is_negative = self.mantissa < 0;
         let prefix = if is_negative { "-" } else { "" };
 
@@ -37,7 +191,7 @@ impl std::fmt::Display for DecimalFraction {
             write!(f, "{}{}{}", prefix, abs_value, "0".repeat(self.exponent as usize))
         } else {
             // For negative exponent, insert decimal point
-            let abs_exp = -self.exponent as usize;
+            let abs_exp = self.exponent.unsigned_abs() as usize;
             let value_str = abs_value.to_string();
 
             if value_str.len() <= abs_exp {
@@ -54,6 +208,291 @@ impl std::fmt::Display for DecimalFraction {
     }
 }
 
+/// Splits a decimal string in plain or scientific notation into its sign,
+/// its significant digits with leading zeros removed (empty for zero), and
+/// the power of ten those digits are scaled by.
+pub(crate) fn parse_decimal_parts(s: &str) -> Result<(bool, String, i64)> {
+    let invalid = |reason: &str| Error::InvalidDecimalFraction {
+        input: s.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let (negative, rest) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    let (number, exponent_part) = match rest.find(['e', 'E']) {
+        Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+        None => (rest, None),
+    };
+
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(invalid("no digits"));
+    }
+    if !integer.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(invalid("unexpected character"));
+    }
+
+    let exponent = match exponent_part {
+        None => 0i64,
+        Some(e) => {
+            let digits = e.strip_prefix(['+', '-']).unwrap_or(e);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid("malformed exponent"));
+            }
+            // Anything this long is out of range no matter the mantissa.
+            e.parse::<i64>().map_err(|_| Error::ExponentOverflow)?
+        }
+    };
+    let exponent = exponent
+        .checked_sub(fraction.len() as i64)
+        .ok_or(Error::ExponentOverflow)?;
+
+    let digits = format!("{}{}", integer, fraction);
+    let digits = digits.trim_start_matches('0').to_string();
+    Ok((negative, digits, exponent))
+}
+
+impl std::str::FromStr for DecimalFraction {
+    type Err = Error;
+
+    /// Parses plain (`"-0.005"`, `"123000"`) or scientific (`"1.23e-7"`,
+    /// `"+4E3"`) notation. The digits written become the mantissa, so
+    /// `"1.10"` parses as `4([-2, 110])`; trailing zeros are only moved into
+    /// the exponent when the mantissa would otherwise overflow.
+    fn from_str(s: &str) -> Result<Self> {
+        let (negative, mut digits, mut exponent) = parse_decimal_parts(s)?;
+        if digits.is_empty() {
+            return Ok(Self::new(exponent.clamp(i8::MIN as i64, i8::MAX as i64) as i8, 0));
+        }
+
+        // An exponent that is too small can be balanced by trailing zeros.
+        while exponent < i8::MIN as i64 && digits.ends_with('0') {
+            digits.pop();
+            exponent += 1;
+        }
+
+        // Digits beyond what an i64 can hold are only acceptable if they
+        // are trailing zeros.
+        let sign = if negative { "-" } else { "" };
+        let mut mantissa = format!("{}{}", sign, digits).parse::<i64>();
+        if mantissa.is_err() {
+            let trimmed = digits.trim_end_matches('0');
+            exponent += (digits.len() - trimmed.len()) as i64;
+            mantissa = format!("{}{}", sign, trimmed).parse::<i64>();
+        }
+        let mut mantissa = mantissa.map_err(|_| Error::MantissaOverflow)?;
+
+        // An exponent that is too large can be absorbed into the mantissa.
+        if exponent > i8::MAX as i64 {
+            let shift = u32::try_from(exponent - i8::MAX as i64)
+                .map_err(|_| Error::ExponentOverflow)?;
+            mantissa = scale_mantissa(mantissa, shift).map_err(|_| Error::ExponentOverflow)?;
+            exponent = i8::MAX as i64;
+        }
+        let exponent = i8::try_from(exponent).map_err(|_| Error::ExponentOverflow)?;
+
+        Ok(Self::new(exponent, mantissa))
+    }
+}
+
+impl DecimalFraction {
+    /// Converts an `f64` to the shortest decimal fraction that converts back
+    /// to the same `f64`, so `0.1` becomes `4([-1, 1])` rather than its full
+    /// binary expansion. Fails on NaN, infinities, and values whose decimal
+    /// exponent doesn't fit in an `i8`.
+    pub fn from_f64_shortest(value: f64) -> Result<Self> {
+        if !value.is_finite() {
+            return Err(Error::NonFiniteFloat(value));
+        }
+        // Rust's float formatting produces the shortest round-tripping digits.
+        format!("{:e}", value).parse()
+    }
+
+    /// Converts an `f64` to the decimal fraction that is exactly equal to it,
+    /// so `0.5` becomes `4([-1, 5])`. Every finite `f64` is a terminating
+    /// decimal, but most (including `0.1`) need far more digits than an
+    /// `i64` mantissa holds, in which case this fails. Large integers such
+    /// as `1e22` keep their trailing zeros in the exponent: `4([22, 1])`.
+    pub fn from_f64_exact(value: f64) -> Result<Self> {
+        BigFloat::from_f64(value)?.try_into()
+    }
+}
+
+impl DecimalFraction {
+    /// Compares the numeric values of `self` and `other`, so `1.10` and `1.1`
+    /// are equal. Never overflows, whatever the exponents.
+    pub fn numeric_cmp(&self, other: &Self) -> cmp::Ordering {
+        let sign = self.mantissa.signum();
+        match sign.cmp(&other.mantissa.signum()) {
+            cmp::Ordering::Equal if sign == 0 => cmp::Ordering::Equal,
+            cmp::Ordering::Equal if sign > 0 => self.magnitude_cmp(other),
+            cmp::Ordering::Equal => self.magnitude_cmp(other).reverse(),
+            unequal => unequal,
+        }
+    }
+
+    /// Returns `true` if `self` and `other` have the same numeric value.
+    pub fn numeric_eq(&self, other: &Self) -> bool {
+        self.numeric_cmp(other) == cmp::Ordering::Equal
+    }
+
+    /// Compares absolute values. Both mantissas must be nonzero.
+    fn magnitude_cmp(&self, other: &Self) -> cmp::Ordering {
+        let (a, b) = (self.mantissa.unsigned_abs(), other.mantissa.unsigned_abs());
+
+        // Compare the positions of the leading digits first.
+        let lead_a = a.ilog10() as i32 + self.exponent as i32;
+        let lead_b = b.ilog10() as i32 + other.exponent as i32;
+        if lead_a != lead_b {
+            return lead_a.cmp(&lead_b);
+        }
+
+        // With the same leading position the exponents differ by at most 19
+        // digits, so aligning them fits comfortably in a `u128`.
+        let exponent = self.exponent.min(other.exponent);
+        let a = a as u128 * 10u128.pow((self.exponent as i32 - exponent as i32) as u32);
+        let b = b as u128 * 10u128.pow((other.exponent as i32 - exponent as i32) as u32);
+        a.cmp(&b)
+    }
+}
+
+/// Orders by numeric value. Values that are numerically equal but written
+/// with different exponents (`1.10` and `1.1`) are ordered by exponent, which
+/// keeps the ordering consistent with the structural `Eq`. Use
+/// [`DecimalValue`] where numerically equal values should compare equal.
+impl PartialOrd for DecimalFraction {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DecimalFraction {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.numeric_cmp(other).then(self.exponent.cmp(&other.exponent))
+    }
+}
+
+/// A `DecimalFraction` compared by numeric value: `Eq`, `Ord`, and `Hash`
+/// all treat `1.10` and `1.1` as the same value, so it can be used as a
+/// `HashMap` or `BTreeMap` key.
+#[derive(Clone, Copy, Debug)]
+pub struct DecimalValue(DecimalFraction);
+
+impl DecimalValue {
+    pub fn new(decimal: DecimalFraction) -> Self {
+        Self(decimal)
+    }
+
+    pub fn decimal(&self) -> DecimalFraction {
+        self.0
+    }
+}
+
+impl From<DecimalFraction> for DecimalValue {
+    fn from(value: DecimalFraction) -> Self {
+        Self(value)
+    }
+}
+
+impl From<DecimalValue> for DecimalFraction {
+    fn from(value: DecimalValue) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq for DecimalValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.numeric_eq(&other.0)
+    }
+}
+
+impl Eq for DecimalValue {}
+
+impl PartialOrd for DecimalValue {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DecimalValue {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.numeric_cmp(&other.0)
+    }
+}
+
+impl Hash for DecimalValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Numerically equal values share a canonical form.
+        self.0.normalize().hash(state);
+    }
+}
+
+impl std::fmt::Display for DecimalValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::ops::Add for DecimalFraction {
+    type Output = Result<Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+    }
+}
+
+impl std::ops::Sub for DecimalFraction {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+    }
+}
+
+impl std::ops::Mul for DecimalFraction {
+    type Output = Result<Self>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+    }
+}
+
+impl std::ops::Neg for DecimalFraction {
+    type Output = Result<Self>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+impl std::iter::Sum<DecimalFraction> for Result<DecimalFraction> {
+    fn sum<I: Iterator<Item = DecimalFraction>>(mut iter: I) -> Self {
+        iter.try_fold(DecimalFraction::new(0, 0), |acc, x| acc.checked_add(x))
+    }
+}
+
+impl<'a> std::iter::Sum<&'a DecimalFraction> for Result<DecimalFraction> {
+    fn sum<I: Iterator<Item = &'a DecimalFraction>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl std::iter::Product<DecimalFraction> for Result<DecimalFraction> {
+    fn product<I: Iterator<Item = DecimalFraction>>(mut iter: I) -> Self {
+        iter.try_fold(DecimalFraction::new(0, 1), |acc, x| acc.checked_mul(x))
+    }
+}
+
+impl<'a> std::iter::Product<&'a DecimalFraction> for Result<DecimalFraction> {
+    fn product<I: Iterator<Item = &'a DecimalFraction>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
 // ANCHOR: example_7
 impl From<DecimalFraction> for CBOR {
     fn from(value: DecimalFraction) -> Self {
@@ -150,3 +589,271 @@ fn decimal_fraction_display() {
     let boundary2 = DecimalFraction::new(-1, 1);
     assert_eq!(boundary2.to_string(), "0.1");
 }
+
+#[test]
+fn decimal_fraction_checked_arithmetic() {
+    let a = DecimalFraction::new(-2, 110); // 1.10
+    let b = DecimalFraction::new(-1, 25); // 2.5
+
+    assert_eq!(a.checked_add(b).unwrap(), DecimalFraction::new(-2, 360));
+    assert_eq!(a.checked_sub(b).unwrap(), DecimalFraction::new(-2, -140));
+    assert_eq!(a.checked_mul(b).unwrap(), DecimalFraction::new(-3, 2750));
+    assert_eq!(a.checked_neg().unwrap(), DecimalFraction::new(-2, -110));
+
+    // Exponents are aligned exactly, never approximated
+    let big = DecimalFraction::new(3, 7); // 7000
+    let small = DecimalFraction::new(-3, 1); // 0.001
+    assert_eq!((big + small).unwrap().to_string(), "7000.001");
+
+    // Operators and iterator adapters report errors instead of panicking
+    assert_eq!((a + b).unwrap(), DecimalFraction::new(-2, 360));
+    assert_eq!((-a).unwrap(), DecimalFraction::new(-2, -110));
+    let total: Result<DecimalFraction> = [a, b, small].iter().sum();
+    assert_eq!(total.unwrap().to_string(), "3.601");
+    let product: Result<DecimalFraction> = [a, b].into_iter().product();
+    assert_eq!(product.unwrap().to_string(), "2.750");
+    let empty: Result<DecimalFraction> = std::iter::empty::<DecimalFraction>().sum();
+    assert_eq!(empty.unwrap(), DecimalFraction::new(0, 0));
+}
+
+#[test]
+fn decimal_fraction_arithmetic_overflow() {
+    let max = DecimalFraction::new(0, i64::MAX);
+    let one = DecimalFraction::new(0, 1);
+    assert_eq!(max.checked_add(one), Err(Error::MantissaOverflow));
+    assert_eq!(DecimalFraction::new(0, i64::MIN).checked_neg(), Err(Error::MantissaOverflow));
+    assert_eq!(max.checked_mul(DecimalFraction::new(0, 2)), Err(Error::MantissaOverflow));
+
+    // Aligning exponents that are too far apart overflows the mantissa
+    let huge = DecimalFraction::new(100, 1);
+    assert_eq!(huge.checked_add(one), Err(Error::MantissaOverflow));
+
+    // Exponent sums are range-checked
+    let e = DecimalFraction::new(100, 1);
+    assert_eq!(e.checked_mul(e), Err(Error::ExponentOverflow));
+    let e = DecimalFraction::new(-100, 1);
+    assert_eq!(e.checked_mul(e), Err(Error::ExponentOverflow));
+}
+
+#[test]
+fn decimal_fraction_div_with() {
+    use RoundingMode::*;
+
+    let hundred = DecimalFraction::new(-2, 10000); // 100.00
+    let three = DecimalFraction::new(0, 3);
+    assert_eq!(hundred.div_with(three, 2, HalfEven).unwrap().to_string(), "33.33");
+    assert_eq!(hundred.div_with(three, 2, Ceiling).unwrap().to_string(), "33.34");
+    assert_eq!(hundred.div_with(three, 0, HalfEven).unwrap().to_string(), "33");
+    assert_eq!(hundred.div_with(three, -1, HalfEven).unwrap().to_string(), "30");
+
+    // Ties
+    let x = DecimalFraction::new(-3, 1125); // 1.125
+    let one = DecimalFraction::new(0, 1);
+    assert_eq!(x.div_with(one, 2, HalfEven).unwrap().to_string(), "1.12");
+    assert_eq!(x.div_with(one, 2, HalfUp).unwrap().to_string(), "1.13");
+    assert_eq!(x.div_with(one, 2, HalfDown).unwrap().to_string(), "1.12");
+    assert_eq!(x.checked_neg().unwrap().div_with(one, 2, Floor).unwrap().to_string(), "-1.13");
+    assert_eq!(x.checked_neg().unwrap().div_with(one, 2, TowardZero).unwrap().to_string(), "-1.12");
+    assert_eq!(x.checked_neg().unwrap().div_with(one, 2, AwayFromZero).unwrap().to_string(), "-1.13");
+
+    // Unit price: 7.50 / 0.25 = 30
+    let price = DecimalFraction::new(-2, 750);
+    let qty = DecimalFraction::new(-2, 25);
+    assert_eq!(price.div_with(qty, 0, HalfEven).unwrap(), DecimalFraction::new(0, 30));
+
+    // Tiny quotients still honor the rounding direction
+    let tiny = DecimalFraction::new(-100, 1);
+    let huge = DecimalFraction::new(100, 1);
+    assert_eq!(tiny.div_with(huge, 2, HalfEven).unwrap(), DecimalFraction::new(-2, 0));
+    assert_eq!(tiny.div_with(huge, 2, Ceiling).unwrap(), DecimalFraction::new(-2, 1));
+
+    assert_eq!(one.div_with(DecimalFraction::new(-2, 0), 2, HalfEven), Err(Error::DivisionByZero));
+    assert_eq!(huge.div_with(tiny, 0, HalfEven), Err(Error::MantissaOverflow));
+    assert_eq!(one.div_with(one, -128, HalfEven), Err(Error::ExponentOverflow));
+}
+
+#[test]
+fn decimal_fraction_quantize() {
+    use RoundingMode::*;
+
+    // 1.105 to two places
+    let a = DecimalFraction::new(-3, 1105);
+    assert_eq!(a.rescale(2, HalfEven).unwrap(), DecimalFraction::new(-2, 110));
+    assert_eq!(a.rescale(2, HalfUp).unwrap(), DecimalFraction::new(-2, 111));
+    assert_eq!(a.quantize(-2, Floor).unwrap(), DecimalFraction::new(-2, 110));
+
+    // 1.1 to two places is exact
+    let b = DecimalFraction::new(-1, 11);
+    assert_eq!(b.rescale(2, HalfEven).unwrap(), DecimalFraction::new(-2, 110));
+    assert_eq!(a.rescale(2, HalfEven).unwrap(), b.rescale(2, HalfEven).unwrap());
+
+    // Coarser exponents
+    let c = DecimalFraction::new(-2, -12345); // -123.45
+    assert_eq!(c.quantize(1, HalfEven).unwrap().to_string(), "-120");
+    assert_eq!(c.quantize(1, Floor).unwrap().to_string(), "-130");
+    assert_eq!(c.quantize(100, HalfEven).unwrap(), DecimalFraction::new(100, 0));
+    assert_eq!(c.quantize(100, Floor).unwrap(), DecimalFraction::new(100, -1));
+
+    // Finer exponents can overflow
+    assert_eq!(c.quantize(-20, HalfEven), Err(Error::MantissaOverflow));
+    assert_eq!(c.rescale(-128, HalfEven), Err(Error::ExponentOverflow));
+}
+
+#[test]
+fn decimal_fraction_normalize() {
+    assert_eq!(DecimalFraction::new(-1, 10).normalize(), DecimalFraction::new(0, 1));
+    assert_eq!(DecimalFraction::new(-3, 1100).normalize(), DecimalFraction::new(-1, 11));
+    assert_eq!(DecimalFraction::new(2, -5000).normalize(), DecimalFraction::new(5, -5));
+    assert_eq!(DecimalFraction::new(-7, 0).normalize(), DecimalFraction::new(0, 0));
+    assert_eq!(DecimalFraction::new(125, 1000).normalize(), DecimalFraction::new(127, 10));
+
+    assert!(DecimalFraction::new(-1, 11).is_normalized());
+    assert!(!DecimalFraction::new(-2, 110).is_normalized());
+    assert!(DecimalFraction::new(127, 10).is_normalized());
+}
+
+#[test]
+fn decimal_fraction_canonical_cbor() {
+    let one = DecimalFraction::new(0, 1);
+    let one_point_zero = DecimalFraction::new(-1, 10);
+
+    // By default values are encoded exactly as given
+    assert_eq!(one_point_zero.to_cbor().diagnostic_flat(), "4([-1, 10])");
+    assert_ne!(one.to_cbor_data(), one_point_zero.to_cbor_data());
+
+    // The canonical encoding makes equal values encode identically
+    assert_eq!(one_point_zero.to_canonical_cbor().diagnostic_flat(), "4([0, 1])");
+    assert_eq!(one.to_canonical_cbor(), one_point_zero.to_canonical_cbor());
+
+    // Non-canonical input decodes, unless canonical form is required
+    let non_canonical = one_point_zero.to_cbor();
+    assert_eq!(DecimalFraction::try_from(non_canonical.clone()).unwrap(), one_point_zero);
+    assert!(DecimalFraction::try_from_canonical(non_canonical).is_err());
+    assert_eq!(DecimalFraction::try_from_canonical(one.to_cbor()).unwrap(), one);
+}
+
+#[test]
+fn decimal_fraction_from_str() {
+    let parse = |s: &str| s.parse::<DecimalFraction>().unwrap();
+
+    assert_eq!(parse("1.10"), DecimalFraction::new(-2, 110));
+    assert_eq!(parse("-0.005"), DecimalFraction::new(-3, -5));
+    assert_eq!(parse("123000"), DecimalFraction::new(0, 123000));
+    assert_eq!(parse("1.23e-7"), DecimalFraction::new(-9, 123));
+    assert_eq!(parse("+4E3"), DecimalFraction::new(3, 4));
+    assert_eq!(parse(".5"), DecimalFraction::new(-1, 5));
+    assert_eq!(parse("5."), DecimalFraction::new(0, 5));
+    assert_eq!(parse("-0"), DecimalFraction::new(0, 0));
+    assert_eq!(parse("-9223372036854775808"), DecimalFraction::new(0, i64::MIN));
+    assert_eq!(parse("1e128"), DecimalFraction::new(127, 10));
+    assert_eq!(parse("100e-130"), DecimalFraction::new(-128, 1));
+    assert_eq!(parse("-1.200e-126"), DecimalFraction::new(-128, -120));
+
+    for bad in ["", "-", ".", "1.2.3", "1e", "e5", "1e+", " 1", "1,5", "0x10", "1e5.5"] {
+        assert!(
+            matches!(bad.parse::<DecimalFraction>(), Err(Error::InvalidDecimalFraction { .. })),
+            "{bad:?}"
+        );
+    }
+    assert_eq!("9223372036854775808".parse::<DecimalFraction>(), Err(Error::MantissaOverflow));
+    assert_eq!("1e-129".parse::<DecimalFraction>(), Err(Error::ExponentOverflow));
+    assert_eq!("1e99999999999999999999".parse::<DecimalFraction>(), Err(Error::ExponentOverflow));
+}
+
+#[test]
+fn decimal_fraction_display_round_trip() {
+    let values = [
+        DecimalFraction::new(0, 0),
+        DecimalFraction::new(-1, 11),
+        DecimalFraction::new(-3, -5),
+        DecimalFraction::new(3, 123),
+        DecimalFraction::new(127, i64::MAX),
+        DecimalFraction::new(-128, 1),
+        DecimalFraction::new(-128, i64::MAX),
+        DecimalFraction::new(-5, -123456789),
+    ];
+    for value in values {
+        let parsed: DecimalFraction = value.to_string().parse().unwrap();
+        assert_eq!(parsed.normalize(), value.normalize(), "{value}");
+    }
+}
+
+#[test]
+fn decimal_fraction_from_f64() {
+    let shortest = |x: f64| DecimalFraction::from_f64_shortest(x).unwrap();
+    assert_eq!(shortest(0.1), DecimalFraction::new(-1, 1));
+    assert_eq!(shortest(1.1), DecimalFraction::new(-1, 11));
+    assert_eq!(shortest(-123.45), DecimalFraction::new(-2, -12345));
+    assert_eq!(shortest(1e21), DecimalFraction::new(21, 1));
+    assert_eq!(shortest(-0.0), DecimalFraction::new(0, 0));
+    assert_eq!(shortest(0.1 + 0.2), DecimalFraction::new(-17, 30000000000000004));
+    for x in [0.1, 1.1, -123.45, 6.02214076e23, 1.0 / 3.0, f64::MAX / 1e200] {
+        let round_tripped: f64 = shortest(x).to_string().parse().unwrap();
+        assert_eq!(round_tripped.to_bits(), x.to_bits(), "{x}");
+    }
+
+    let exact = |x: f64| DecimalFraction::from_f64_exact(x).unwrap();
+    assert_eq!(exact(0.5), DecimalFraction::new(-1, 5));
+    assert_eq!(exact(-1.25), DecimalFraction::new(-2, -125));
+    assert_eq!(exact(1024.0), DecimalFraction::new(0, 1024));
+    assert_eq!(exact(0.0), DecimalFraction::new(0, 0));
+    assert_eq!(exact(2f64.powi(-20)), DecimalFraction::new(-20, 95367431640625));
+    assert_eq!(exact(1e19), DecimalFraction::new(19, 1));
+    assert_eq!(exact(1e22), DecimalFraction::new(22, 1));
+    assert_eq!(exact(-5e20), DecimalFraction::new(20, -5));
+    assert_eq!(exact(1e22 * 2f64.powi(60)), DecimalFraction::new(22, 1 << 60));
+    assert_eq!(DecimalFraction::from_f64_exact(0.1), Err(Error::MantissaOverflow));
+    assert_eq!(DecimalFraction::from_f64_exact(1e300), Err(Error::MantissaOverflow));
+
+    for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(DecimalFraction::from_f64_shortest(x), Err(Error::NonFiniteFloat(_))));
+        assert!(matches!(DecimalFraction::from_f64_exact(x), Err(Error::NonFiniteFloat(_))));
+    }
+    assert_eq!(DecimalFraction::from_f64_shortest(1e300), Err(Error::ExponentOverflow));
+    assert_eq!(DecimalFraction::from_f64_shortest(5e-324), Err(Error::ExponentOverflow));
+}
+
+#[test]
+fn decimal_fraction_ordering() {
+    use std::collections::{ BTreeMap, HashSet };
+
+    let a = DecimalFraction::new(-2, 110); // 1.10
+    let b = DecimalFraction::new(-1, 11); // 1.1
+    assert_ne!(a, b);
+    assert!(a.numeric_eq(&b));
+    assert_eq!(a.numeric_cmp(&b), cmp::Ordering::Equal);
+
+    // Extreme exponents compare without overflow
+    let huge = DecimalFraction::new(127, 1);
+    let tiny = DecimalFraction::new(-128, i64::MAX);
+    let neg = DecimalFraction::new(127, i64::MIN);
+    assert!(tiny < huge);
+    assert!(neg < tiny);
+    assert!(DecimalFraction::new(0, 0) < tiny);
+    assert!(DecimalFraction::new(-3, -1) < DecimalFraction::new(5, 0));
+    assert!(DecimalFraction::new(-1, -11) < DecimalFraction::new(-2, -109));
+    assert!(DecimalFraction::new(-18, 1_000_000_000_000_000_001) > DecimalFraction::new(0, 1));
+
+    let mut prices = [
+        DecimalFraction::new(0, 2),
+        DecimalFraction::new(-2, -50),
+        a,
+        DecimalFraction::new(-3, 999),
+        b,
+    ];
+    prices.sort();
+    let sorted: Vec<String> = prices.iter().map(|p| p.to_string()).collect();
+    assert_eq!(sorted, ["-0.50", "0.999", "1.10", "1.1", "2"]);
+
+    // DecimalValue treats numerically equal values as the same key
+    let mut set = HashSet::new();
+    set.insert(DecimalValue::from(a));
+    assert!(set.contains(&DecimalValue::from(b)));
+    assert!(set.contains(&DecimalValue::from(DecimalFraction::new(-5, 110000))));
+
+    let mut map = BTreeMap::new();
+    map.insert(DecimalValue::from(a), "first");
+    map.insert(DecimalValue::from(b), "second");
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[&DecimalValue::from(a)], "second");
+}
@@ -0,0 +1,126 @@
+use thiserror::Error;
+
+use crate::Language;
+
+/// Errors produced by the numeric and monetary types in this crate.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Error {
+    /// The result of an operation does not fit in the `i64` mantissa.
+    #[error("decimal fraction mantissa overflow")]
+    MantissaOverflow,
+
+    /// The result of an operation does not fit in the `i8` exponent.
+    #[error("decimal fraction exponent overflow")]
+    ExponentOverflow,
+
+    /// A decimal fraction was divided by zero.
+    #[error("division by zero")]
+    DivisionByZero,
+
+    /// A decimal fraction was not in canonical (normalized) form where
+    /// canonical form was required.
+    #[error("decimal fraction {0} is not in canonical form")]
+    NonCanonicalDecimalFraction(String),
+
+    /// A string could not be parsed as a decimal fraction.
+    #[error("invalid decimal fraction {input:?}: {reason}")]
+    InvalidDecimalFraction { input: String, reason: String },
+
+    /// A NaN or infinite `f64` cannot be represented as a decimal fraction.
+    #[error("cannot convert non-finite value {0} to a decimal fraction")]
+    NonFiniteFloat(f64),
+
+    /// A value cannot be converted to `f64` without rounding, overflow, or
+    /// underflow.
+    #[error("{0} is not exactly representable as an f64")]
+    InexactFloat(String),
+
+    /// A currency code is not 2 to 10 upper-case ASCII letters or digits.
+    #[error("invalid currency code {0:?}")]
+    InvalidCurrencyCode(String),
+
+    /// A custom currency was registered with more minor units than an `i64`
+    /// count of minor units can have decimal places.
+    #[error("currency {code} has {minor_units} minor units; at most 18 are supported")]
+    InvalidMinorUnits { code: String, minor_units: u8 },
+
+    /// A well-formed currency code is neither a known ISO 4217 code nor a
+    /// registered custom currency.
+    #[error("unknown currency code {0:?}")]
+    UnknownCurrencyCode(String),
+
+    /// A currency code is known, but was withdrawn before the date it was
+    /// validated against.
+    #[error("currency code {code:?} was withdrawn on {valid_until}")]
+    WithdrawnCurrencyCode { code: String, valid_until: String },
+
+    /// A currency code is known, but was introduced after the date it was
+    /// validated against.
+    #[error("currency code {code:?} is not valid before {valid_from}")]
+    CurrencyCodeNotYetValid { code: String, valid_from: String },
+
+    /// An operation combined amounts in two different currencies.
+    #[error("currency mismatch: {left} and {right}")]
+    CurrencyMismatch { left: String, right: String },
+
+    /// A currency is unknown or, like XAU, has no minor unit, so its
+    /// amounts can't be counted in minor units.
+    #[error("currency {0} has no minor unit")]
+    NoMinorUnit(String),
+
+    /// An amount has digits below its currency's minor unit, which
+    /// converting to minor units would drop.
+    #[error("{amount} has more than {minor_units} decimal places")]
+    ExcessPrecision { amount: String, minor_units: u8 },
+
+    /// An allocation was requested with no ratios, or ratios that are all
+    /// zero.
+    #[error("invalid allocation: {0}")]
+    InvalidAllocation(String),
+
+    /// An empty sequence of currency amounts was summed, so the currency of
+    /// the result is unknown.
+    #[error("cannot sum an empty sequence of currency amounts")]
+    EmptyCurrencyAmountSum,
+
+    /// A rate table has no direct, inverse, or pivot rate between two
+    /// currencies.
+    #[error("no exchange rate from {base} to {quote}")]
+    NoExchangeRate { base: String, quote: String },
+
+    /// A string could not be parsed as a currency amount.
+    #[error("invalid currency amount {input:?}: {reason}")]
+    InvalidCurrencyAmount { input: String, reason: String },
+
+    /// A currency symbol is shared by several currencies, such as `$`, and
+    /// no locale was given to resolve it.
+    #[error("ambiguous currency symbol {0:?}; use a currency code or a locale")]
+    AmbiguousCurrencySymbol(String),
+
+    /// A currency has no unit names in a language, so amounts in it can't
+    /// be spelled out.
+    #[error("no {language:?} words for currency {code}")]
+    NoCurrencyWords { code: String, language: Language },
+
+    /// A locale identifier is not one of the supported locales.
+    #[error("unknown locale {0:?}")]
+    UnknownLocale(String),
+
+    /// A numeric currency code is neither a known ISO 4217 code nor the
+    /// numeric code of a registered custom currency.
+    #[error("unknown numeric currency code {0}")]
+    UnknownNumericCurrencyCode(u64),
+
+    /// A custom currency definition conflicts with ISO 4217 or with a
+    /// currency that is already registered.
+    #[error("currency {0} conflicts with an existing definition")]
+    CurrencyCollision(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl From<Error> for dcbor::Error {
+    fn from(error: Error) -> Self {
+        dcbor::Error::msg(error.to_string())
+    }
+}
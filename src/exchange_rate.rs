@@ -0,0 +1,225 @@
+use dcbor::{ Date, prelude::* };
+
+use crate::{
+    CurrencyAmount,
+    CurrencyCode,
+    CurrencyCodeEncoding,
+    DecimalFraction,
+    Error,
+    Result,
+    RoundingMode,
+    tags::TAG_EXCHANGE_RATE,
+};
+#[cfg(test)]
+use crate::currency_amount::amount;
+
+/// The price of one unit of the `base` currency in the `quote` currency, as
+/// of a point in time: EUR/USD at 1.0834 means 1 EUR buys 1.0834 USD.
+///
+/// Encoded as `33002([base, quote, rate, as_of])`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExchangeRate {
+    base: CurrencyCode,
+    quote: CurrencyCode,
+    rate: DecimalFraction,
+    as_of: Date,
+}
+
+impl ExchangeRate {
+    pub fn new(base: CurrencyCode, quote: CurrencyCode, rate: DecimalFraction, as_of: Date) -> Self {
+        Self { base, quote, rate, as_of }
+    }
+
+    pub fn base(&self) -> &CurrencyCode {
+        &self.base
+    }
+
+    pub fn quote(&self) -> &CurrencyCode {
+        &self.quote
+    }
+
+    pub fn rate(&self) -> &DecimalFraction {
+        &self.rate
+    }
+
+    pub fn as_of(&self) -> &Date {
+        &self.as_of
+    }
+
+    /// Encodes like `From<ExchangeRate> for CBOR`, with both currency codes
+    /// in the given form.
+    pub fn to_cbor_with(&self, encoding: CurrencyCodeEncoding) -> CBOR {
+        let v = vec![
+            self.base.to_cbor_with(encoding),
+            self.quote.to_cbor_with(encoding),
+            self.rate.to_cbor(),
+            self.as_of.to_cbor(),
+        ].to_cbor();
+        CBOR::to_tagged_value(TAG_EXCHANGE_RATE, v)
+    }
+}
+
+impl CurrencyAmount {
+    /// Converts this amount to the rate's quote currency, rounding with
+    /// `mode` to the quote currency's minor units. If the quote currency has
+    /// no minor unit or is unknown, the exact product is returned.
+    ///
+    /// The product is computed exactly before rounding once, so the result
+    /// depends only on the inputs. Fails with [`Error::CurrencyMismatch`] if
+    /// this amount isn't in the rate's base currency.
+    pub fn convert(&self, rate: &ExchangeRate, mode: RoundingMode) -> Result<Self> {
+        if self.currency() != rate.base() {
+            return Err(Error::CurrencyMismatch {
+                left: self.currency().to_string(),
+                right: rate.base().to_string(),
+            });
+        }
+
+        let amount = self.amount();
+        let Some(minor_units) = rate.quote().minor_units() else {
+            return Ok(Self::new(rate.quote().clone(), amount.checked_mul(*rate.rate())?));
+        };
+
+        // Both mantissas are below 2^63, so the product fits in an i128.
+        let product = amount.mantissa as i128 * rate.rate().mantissa as i128;
+        let exponent = amount.exponent as i32 + rate.rate().exponent as i32;
+        let target = -i32::from(minor_units);
+
+        let mantissa = if product == 0 {
+            // Zero at any exponent is zero at the target exponent.
+            0
+        } else if exponent >= target {
+            10i128
+                .checked_pow((exponent - target) as u32)
+                .and_then(|p| product.checked_mul(p))
+                .ok_or(Error::MantissaOverflow)?
+        } else {
+            mode.div_pow10(product, (target - exponent) as u32)
+        };
+        let mantissa = i64::try_from(mantissa).map_err(|_| Error::MantissaOverflow)?;
+
+        let target = i8::try_from(target).map_err(|_| Error::ExponentOverflow)?;
+        Ok(Self::new(rate.quote().clone(), DecimalFraction::new(target, mantissa)))
+    }
+}
+
+impl From<ExchangeRate> for CBOR {
+    fn from(value: ExchangeRate) -> Self {
+        let v = vec![
+            value.base.to_cbor(),
+            value.quote.to_cbor(),
+            value.rate.to_cbor(),
+            value.as_of.to_cbor(),
+        ].to_cbor();
+        CBOR::to_tagged_value(TAG_EXCHANGE_RATE, v)
+    }
+}
+
+impl TryFrom<CBOR> for ExchangeRate {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self, Self::Error> {
+        let item = cbor.try_into_expected_tagged_value(TAG_EXCHANGE_RATE)?;
+        let arr = item.try_into_array()?;
+
+        if arr.len() != 4 {
+            return Err("Expected a four-element array".into());
+        }
+
+        let base: CurrencyCode = arr[0].clone().try_into()?;
+        let quote: CurrencyCode = arr[1].clone().try_into()?;
+        let rate: DecimalFraction = arr[2].clone().try_into()?;
+        let as_of: Date = arr[3].clone().try_into()?;
+
+        Ok(ExchangeRate::new(base, quote, rate, as_of))
+    }
+}
+
+impl std::fmt::Display for ExchangeRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} {} as of {}", self.base, self.quote, self.rate, self.as_of)
+    }
+}
+
+#[test]
+fn exchange_rate_cbor() {
+    let rate = ExchangeRate::new(
+        CurrencyCode::new("EUR"),
+        CurrencyCode::new("USD"),
+        "1.0834".parse().unwrap(),
+        Date::from_ymd(2025, 6, 30),
+    );
+    assert_eq!(rate.to_string(), "EUR/USD 1.0834 as of 2025-06-30");
+
+    let cbor = rate.to_cbor();
+    assert_eq!(
+        cbor.diagnostic_flat(),
+        r#"33002([33000("EUR"), 33000("USD"), 4([-4, 10834]), 1(1751241600)])"#
+    );
+    let decoded: ExchangeRate = cbor.try_into().unwrap();
+    assert_eq!(decoded, rate);
+
+    let numeric = rate.to_cbor_with(CurrencyCodeEncoding::Numeric);
+    assert_eq!(numeric.diagnostic_flat(), "33002([33000(978), 33000(840), 4([-4, 10834]), 1(1751241600)])");
+    assert_eq!(ExchangeRate::try_from(numeric).unwrap(), rate);
+
+    let short = CBOR::to_tagged_value(TAG_EXCHANGE_RATE, vec![CurrencyCode::new("EUR")]);
+    assert!(ExchangeRate::try_from(short).is_err());
+}
+
+#[test]
+fn exchange_rate_convert() {
+    let as_of = Date::from_ymd(2025, 6, 30);
+    let rate = |base: &str, quote: &str, rate: &str| {
+        ExchangeRate::new(CurrencyCode::new(base), CurrencyCode::new(quote), rate.parse().unwrap(), as_of.clone())
+    };
+
+    let eur_usd = rate("EUR", "USD", "1.0834");
+    let converted = amount("EUR", "100.25").convert(&eur_usd, RoundingMode::HalfEven).unwrap();
+    assert_eq!(converted.to_string(), "USD 108.61"); // 108.610850
+    let converted = amount("EUR", "-0.50").convert(&eur_usd, RoundingMode::Floor).unwrap();
+    assert_eq!(converted.to_string(), "USD -0.55"); // -0.5417
+
+    // Rounding to the quote currency's minor units, which may add zeros
+    let usd_jpy = rate("USD", "JPY", "157.235");
+    assert_eq!(amount("USD", "19.99").convert(&usd_jpy, RoundingMode::HalfUp).unwrap().to_string(), "JPY 3143");
+    let usd_bhd = rate("USD", "BHD", "0.376");
+    let converted = amount("USD", "2").convert(&usd_bhd, RoundingMode::HalfEven).unwrap();
+    assert_eq!(converted.amount(), &DecimalFraction::new(-3, 752));
+
+    // No minor unit: the exact product
+    let usd_xau = rate("USD", "XAU", "0.000301");
+    assert_eq!(amount("USD", "1000").convert(&usd_xau, RoundingMode::HalfEven).unwrap().to_string(), "XAU 0.301000");
+
+    // Large values round correctly without overflowing the intermediate product
+    let big = amount("EUR", "90000000000000.00");
+    let converted = big.convert(&rate("EUR", "USD", "1.08340000001"), RoundingMode::HalfEven).unwrap();
+    assert_eq!(converted.to_string(), "USD 97506000000900.00");
+    assert_eq!(
+        big.convert(&rate("EUR", "JPY", "1000000"), RoundingMode::HalfEven),
+        Err(Error::MantissaOverflow)
+    );
+
+    // Tiny values round correctly even when the shift exceeds 38 digits
+    let tiny = CurrencyAmount::new(CurrencyCode::new("EUR"), DecimalFraction::new(-21, 9_000_000_000_000_000_000));
+    let tiny_rate = ExchangeRate::new(
+        CurrencyCode::new("EUR"),
+        CurrencyCode::new("USD"),
+        DecimalFraction::new(-20, 9_000_000_000_000_000_000),
+        as_of.clone(),
+    );
+    let converted = tiny.convert(&tiny_rate, RoundingMode::HalfEven).unwrap();
+    assert_eq!(*converted.amount(), DecimalFraction::new(-2, 0)); // 0.00081
+    let converted = tiny.convert(&tiny_rate, RoundingMode::Ceiling).unwrap();
+    assert_eq!(*converted.amount(), DecimalFraction::new(-2, 1));
+
+    // Zero converts to zero, whatever its exponent
+    let zero = CurrencyAmount::new(CurrencyCode::new("EUR"), DecimalFraction::new(100, 0));
+    let converted = zero.convert(&eur_usd, RoundingMode::HalfEven).unwrap();
+    assert_eq!(*converted.amount(), DecimalFraction::new(-2, 0));
+
+    assert_eq!(
+        amount("GBP", "1").convert(&eur_usd, RoundingMode::HalfEven),
+        Err(Error::CurrencyMismatch { left: "GBP".into(), right: "EUR".into() })
+    );
+}
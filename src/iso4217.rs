@@ -0,0 +1,320 @@
+/// A calendar date as `(year, month, day)`, since `dcbor::Date` can't be
+/// built in a `const` context.
+pub(crate) type Ymd = (i32, u32, u32);
+
+/// One row of the ISO 4217 table of current and historical currency codes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Iso4217Entry {
+    pub code: &'static str,
+    pub numeric: u16,
+    /// `None` where ISO lists the minor unit as "N.A." (precious metals,
+    /// bond market units, testing and no-currency codes).
+    pub minor_units: Option<u8>,
+    /// The local symbol, if the currency has one other than its code. Many
+    /// currencies share a symbol (`$`), so this is for display, not lookup.
+    pub symbol: Option<&'static str>,
+    pub name: &'static str,
+    /// The first day the code is valid, where known. `None` for codes that
+    /// predate the dates we track.
+    pub valid_from: Option<Ymd>,
+    /// The day the code was withdrawn, or `None` if it is still active.
+    pub valid_until: Option<Ymd>,
+}
+
+const fn entry(
+    code: &'static str,
+    numeric: u16,
+    minor_units: Option<u8>,
+    symbol: Option<&'static str>,
+    name: &'static str
+) -> Iso4217Entry {
+    Iso4217Entry { code, numeric, minor_units, symbol, name, valid_from: None, valid_until: None }
+}
+
+/// An entry with a validity period: a recently introduced or a withdrawn
+/// code.
+const fn dated(
+    code: &'static str,
+    numeric: u16,
+    minor_units: Option<u8>,
+    symbol: Option<&'static str>,
+    name: &'static str,
+    valid_from: Option<Ymd>,
+    valid_until: Option<Ymd>
+) -> Iso4217Entry {
+    Iso4217Entry { code, numeric, minor_units, symbol, name, valid_from, valid_until }
+}
+
+/// The ISO 4217 currencies, current as of the 2025 amendments, sorted by
+/// alphabetic code. Codes from the ISO 4217 list of historic denominations
+/// that still turn up in archival data, such as the euro's legacy
+/// currencies, are included with the date they were withdrawn. A numeric
+/// code may be reused by a later currency, as ANG's 532 was by XCG.
+pub(crate) static ISO_4217: &[Iso4217Entry] = &[
+    dated("ADP", 20, Some(0), None, "Andorran Peseta", None, Some((2003, 7, 1))),
+    entry("AED", 784, Some(2), Some("د.إ"), "UAE Dirham"),
+    entry("AFN", 971, Some(2), Some("؋"), "Afghani"),
+    entry("ALL", 8, Some(2), Some("L"), "Lek"),
+    entry("AMD", 51, Some(2), Some("֏"), "Armenian Dram"),
+    dated("ANG", 532, Some(2), Some("ƒ"), "Netherlands Antillean Guilder", None, Some((2025, 7, 1))),
+    entry("AOA", 973, Some(2), Some("Kz"), "Kwanza"),
+    entry("ARS", 32, Some(2), Some("$"), "Argentine Peso"),
+    dated("ATS", 40, Some(2), Some("S"), "Schilling", None, Some((2002, 3, 1))),
+    entry("AUD", 36, Some(2), Some("$"), "Australian Dollar"),
+    entry("AWG", 533, Some(2), Some("ƒ"), "Aruban Florin"),
+    dated("AZM", 31, Some(2), None, "Azerbaijanian Manat", None, Some((2006, 1, 1))),
+    entry("AZN", 944, Some(2), Some("₼"), "Azerbaijan Manat"),
+    entry("BAM", 977, Some(2), Some("KM"), "Convertible Mark"),
+    entry("BBD", 52, Some(2), Some("$"), "Barbados Dollar"),
+    entry("BDT", 50, Some(2), Some("৳"), "Taka"),
+    dated("BEF", 56, Some(0), Some("fr."), "Belgian Franc", None, Some((2002, 3, 1))),
+    dated("BGN", 975, Some(2), Some("лв"), "Bulgarian Lev", None, Some((2026, 1, 1))),
+    entry("BHD", 48, Some(3), Some(".د.ب"), "Bahraini Dinar"),
+    entry("BIF", 108, Some(0), Some("FBu"), "Burundi Franc"),
+    entry("BMD", 60, Some(2), Some("$"), "Bermudian Dollar"),
+    entry("BND", 96, Some(2), Some("$"), "Brunei Dollar"),
+    entry("BOB", 68, Some(2), Some("Bs"), "Boliviano"),
+    entry("BOV", 984, Some(2), None, "Mvdol"),
+    entry("BRL", 986, Some(2), Some("R$"), "Brazilian Real"),
+    entry("BSD", 44, Some(2), Some("$"), "Bahamian Dollar"),
+    entry("BTN", 64, Some(2), Some("Nu."), "Ngultrum"),
+    entry("BWP", 72, Some(2), Some("P"), "Pula"),
+    dated("BYN", 933, Some(2), Some("Br"), "Belarusian Ruble", Some((2016, 7, 1)), None),
+    dated("BYR", 974, Some(0), Some("Br"), "Belarusian Ruble", None, Some((2017, 1, 1))),
+    entry("BZD", 84, Some(2), Some("$"), "Belize Dollar"),
+    entry("CAD", 124, Some(2), Some("$"), "Canadian Dollar"),
+    entry("CDF", 976, Some(2), Some("FC"), "Congolese Franc"),
+    entry("CHE", 947, Some(2), None, "WIR Euro"),
+    entry("CHF", 756, Some(2), Some("CHF"), "Swiss Franc"),
+    entry("CHW", 948, Some(2), None, "WIR Franc"),
+    entry("CLF", 990, Some(4), None, "Unidad de Fomento"),
+    entry("CLP", 152, Some(0), Some("$"), "Chilean Peso"),
+    entry("CNY", 156, Some(2), Some("¥"), "Yuan Renminbi"),
+    entry("COP", 170, Some(2), Some("$"), "Colombian Peso"),
+    entry("COU", 970, Some(2), None, "Unidad de Valor Real"),
+    entry("CRC", 188, Some(2), Some("₡"), "Costa Rican Colon"),
+    dated("CSD", 891, Some(2), None, "Serbian Dinar", None, Some((2006, 10, 1))),
+    entry("CUC", 931, Some(2), Some("$"), "Peso Convertible"),
+    entry("CUP", 192, Some(2), Some("$"), "Cuban Peso"),
+    entry("CVE", 132, Some(2), Some("Esc"), "Cabo Verde Escudo"),
+    dated("CYP", 196, Some(2), Some("£"), "Cyprus Pound", None, Some((2008, 1, 1))),
+    entry("CZK", 203, Some(2), Some("Kč"), "Czech Koruna"),
+    dated("DEM", 276, Some(2), Some("DM"), "Deutsche Mark", None, Some((2002, 3, 1))),
+    entry("DJF", 262, Some(0), Some("Fdj"), "Djibouti Franc"),
+    entry("DKK", 208, Some(2), Some("kr"), "Danish Krone"),
+    entry("DOP", 214, Some(2), Some("$"), "Dominican Peso"),
+    entry("DZD", 12, Some(2), Some("دج"), "Algerian Dinar"),
+    dated("ECS", 218, Some(0), Some("S/."), "Sucre", None, Some((2000, 9, 1))),
+    dated("EEK", 233, Some(2), Some("kr"), "Kroon", None, Some((2011, 1, 1))),
+    entry("EGP", 818, Some(2), Some("E£"), "Egyptian Pound"),
+    entry("ERN", 232, Some(2), Some("Nfk"), "Nakfa"),
+    dated("ESP", 724, Some(0), Some("Pta"), "Spanish Peseta", None, Some((2002, 3, 1))),
+    entry("ETB", 230, Some(2), Some("Br"), "Ethiopian Birr"),
+    dated("EUR", 978, Some(2), Some("€"), "Euro", Some((1999, 1, 1)), None),
+    dated("FIM", 246, Some(2), Some("mk"), "Markka", None, Some((2002, 3, 1))),
+    entry("FJD", 242, Some(2), Some("$"), "Fiji Dollar"),
+    entry("FKP", 238, Some(2), Some("£"), "Falkland Islands Pound"),
+    dated("FRF", 250, Some(2), Some("F"), "French Franc", None, Some((2002, 3, 1))),
+    entry("GBP", 826, Some(2), Some("£"), "Pound Sterling"),
+    entry("GEL", 981, Some(2), Some("₾"), "Lari"),
+    dated("GHC", 288, Some(2), Some("₵"), "Cedi", None, Some((2008, 1, 1))),
+    dated("GHS", 936, Some(2), Some("₵"), "Ghana Cedi", Some((2007, 7, 1)), None),
+    entry("GIP", 292, Some(2), Some("£"), "Gibraltar Pound"),
+    entry("GMD", 270, Some(2), Some("D"), "Dalasi"),
+    entry("GNF", 324, Some(0), Some("FG"), "Guinean Franc"),
+    dated("GRD", 300, Some(0), Some("₯"), "Drachma", None, Some((2002, 3, 1))),
+    entry("GTQ", 320, Some(2), Some("Q"), "Quetzal"),
+    entry("GYD", 328, Some(2), Some("$"), "Guyana Dollar"),
+    entry("HKD", 344, Some(2), Some("$"), "Hong Kong Dollar"),
+    entry("HNL", 340, Some(2), Some("L"), "Lempira"),
+    dated("HRK", 191, Some(2), Some("kn"), "Kuna", None, Some((2023, 1, 1))),
+    entry("HTG", 332, Some(2), Some("G"), "Gourde"),
+    entry("HUF", 348, Some(2), Some("Ft"), "Forint"),
+    entry("IDR", 360, Some(2), Some("Rp"), "Rupiah"),
+    dated("IEP", 372, Some(2), Some("£"), "Irish Pound", None, Some((2002, 3, 1))),
+    entry("ILS", 376, Some(2), Some("₪"), "New Israeli Sheqel"),
+    entry("INR", 356, Some(2), Some("₹"), "Indian Rupee"),
+    entry("IQD", 368, Some(3), Some("ع.د"), "Iraqi Dinar"),
+    entry("IRR", 364, Some(2), Some("﷼"), "Iranian Rial"),
+    entry("ISK", 352, Some(0), Some("kr"), "Iceland Krona"),
+    dated("ITL", 380, Some(0), Some("₤"), "Italian Lira", None, Some((2002, 3, 1))),
+    entry("JMD", 388, Some(2), Some("$"), "Jamaican Dollar"),
+    entry("JOD", 400, Some(3), Some("د.ا"), "Jordanian Dinar"),
+    entry("JPY", 392, Some(0), Some("¥"), "Yen"),
+    entry("KES", 404, Some(2), Some("KSh"), "Kenyan Shilling"),
+    entry("KGS", 417, Some(2), Some("с"), "Som"),
+    entry("KHR", 116, Some(2), Some("៛"), "Riel"),
+    entry("KMF", 174, Some(0), Some("CF"), "Comorian Franc"),
+    entry("KPW", 408, Some(2), Some("₩"), "North Korean Won"),
+    entry("KRW", 410, Some(0), Some("₩"), "Won"),
+    entry("KWD", 414, Some(3), Some("د.ك"), "Kuwaiti Dinar"),
+    entry("KYD", 136, Some(2), Some("$"), "Cayman Islands Dollar"),
+    entry("KZT", 398, Some(2), Some("₸"), "Tenge"),
+    entry("LAK", 418, Some(2), Some("₭"), "Lao Kip"),
+    entry("LBP", 422, Some(2), Some("ل.ل"), "Lebanese Pound"),
+    entry("LKR", 144, Some(2), Some("Rs"), "Sri Lanka Rupee"),
+    entry("LRD", 430, Some(2), Some("$"), "Liberian Dollar"),
+    entry("LSL", 426, Some(2), Some("L"), "Loti"),
+    dated("LTL", 440, Some(2), Some("Lt"), "Lithuanian Litas", None, Some((2015, 1, 1))),
+    dated("LUF", 442, Some(0), Some("F"), "Luxembourg Franc", None, Some((2002, 3, 1))),
+    dated("LVL", 428, Some(2), Some("Ls"), "Latvian Lats", None, Some((2014, 1, 1))),
+    entry("LYD", 434, Some(3), Some("ل.د"), "Libyan Dinar"),
+    entry("MAD", 504, Some(2), Some("د.م."), "Moroccan Dirham"),
+    entry("MDL", 498, Some(2), Some("L"), "Moldovan Leu"),
+    entry("MGA", 969, Some(2), Some("Ar"), "Malagasy Ariary"),
+    dated("MGF", 450, Some(0), Some("FMG"), "Malagasy Franc", None, Some((2005, 1, 1))),
+    entry("MKD", 807, Some(2), Some("ден"), "Denar"),
+    entry("MMK", 104, Some(2), Some("K"), "Kyat"),
+    entry("MNT", 496, Some(2), Some("₮"), "Tugrik"),
+    entry("MOP", 446, Some(2), Some("MOP$"), "Pataca"),
+    dated("MRO", 478, Some(2), Some("UM"), "Ouguiya", None, Some((2018, 1, 1))),
+    dated("MRU", 929, Some(2), Some("UM"), "Ouguiya", Some((2018, 1, 1)), None),
+    dated("MTL", 470, Some(2), Some("Lm"), "Maltese Lira", None, Some((2008, 1, 1))),
+    entry("MUR", 480, Some(2), Some("Rs"), "Mauritius Rupee"),
+    entry("MVR", 462, Some(2), Some("Rf"), "Rufiyaa"),
+    entry("MWK", 454, Some(2), Some("MK"), "Malawi Kwacha"),
+    entry("MXN", 484, Some(2), Some("$"), "Mexican Peso"),
+    entry("MXV", 979, Some(2), None, "Mexican Unidad de Inversion (UDI)"),
+    entry("MYR", 458, Some(2), Some("RM"), "Malaysian Ringgit"),
+    dated("MZM", 508, Some(2), Some("MT"), "Mozambique Metical", None, Some((2006, 7, 1))),
+    dated("MZN", 943, Some(2), Some("MT"), "Mozambique Metical", Some((2006, 7, 1)), None),
+    entry("NAD", 516, Some(2), Some("$"), "Namibia Dollar"),
+    entry("NGN", 566, Some(2), Some("₦"), "Naira"),
+    entry("NIO", 558, Some(2), Some("C$"), "Cordoba Oro"),
+    dated("NLG", 528, Some(2), Some("ƒ"), "Netherlands Guilder", None, Some((2002, 3, 1))),
+    entry("NOK", 578, Some(2), Some("kr"), "Norwegian Krone"),
+    entry("NPR", 524, Some(2), Some("Rs"), "Nepalese Rupee"),
+    entry("NZD", 554, Some(2), Some("$"), "New Zealand Dollar"),
+    entry("OMR", 512, Some(3), Some("ر.ع."), "Rial Omani"),
+    entry("PAB", 590, Some(2), Some("B/."), "Balboa"),
+    entry("PEN", 604, Some(2), Some("S/"), "Sol"),
+    entry("PGK", 598, Some(2), Some("K"), "Kina"),
+    entry("PHP", 608, Some(2), Some("₱"), "Philippine Peso"),
+    entry("PKR", 586, Some(2), Some("Rs"), "Pakistan Rupee"),
+    entry("PLN", 985, Some(2), Some("zł"), "Zloty"),
+    dated("PTE", 620, Some(0), Some("Esc"), "Portuguese Escudo", None, Some((2002, 3, 1))),
+    entry("PYG", 600, Some(0), Some("₲"), "Guarani"),
+    entry("QAR", 634, Some(2), Some("ر.ق"), "Qatari Rial"),
+    dated("ROL", 642, Some(2), Some("lei"), "Leu", None, Some((2005, 7, 1))),
+    dated("RON", 946, Some(2), Some("lei"), "Romanian Leu", Some((2005, 7, 1)), None),
+    entry("RSD", 941, Some(2), Some("дин."), "Serbian Dinar"),
+    entry("RUB", 643, Some(2), Some("₽"), "Russian Ruble"),
+    entry("RWF", 646, Some(0), Some("RF"), "Rwanda Franc"),
+    entry("SAR", 682, Some(2), Some("ر.س"), "Saudi Riyal"),
+    entry("SBD", 90, Some(2), Some("$"), "Solomon Islands Dollar"),
+    entry("SCR", 690, Some(2), Some("Rs"), "Seychelles Rupee"),
+    dated("SDD", 736, Some(2), None, "Sudanese Dinar", None, Some((2007, 7, 1))),
+    entry("SDG", 938, Some(2), Some("ج.س."), "Sudanese Pound"),
+    entry("SEK", 752, Some(2), Some("kr"), "Swedish Krona"),
+    entry("SGD", 702, Some(2), Some("$"), "Singapore Dollar"),
+    entry("SHP", 654, Some(2), Some("£"), "Saint Helena Pound"),
+    dated("SIT", 705, Some(2), Some("SIT"), "Tolar", None, Some((2007, 1, 1))),
+    dated("SKK", 703, Some(2), Some("Sk"), "Slovak Koruna", None, Some((2009, 1, 1))),
+    dated("SLE", 925, Some(2), Some("Le"), "Leone", Some((2022, 7, 1)), None),
+    dated("SLL", 694, Some(2), Some("Le"), "Leone", None, Some((2024, 1, 1))),
+    entry("SOS", 706, Some(2), Some("Sh"), "Somali Shilling"),
+    entry("SRD", 968, Some(2), Some("$"), "Surinam Dollar"),
+    dated("SRG", 740, Some(2), Some("ƒ"), "Surinam Guilder", None, Some((2004, 1, 1))),
+    entry("SSP", 728, Some(2), Some("£"), "South Sudanese Pound"),
+    dated("STD", 678, Some(2), Some("Db"), "Dobra", None, Some((2018, 1, 1))),
+    dated("STN", 930, Some(2), Some("Db"), "Dobra", Some((2018, 1, 1)), None),
+    entry("SVC", 222, Some(2), Some("₡"), "El Salvador Colon"),
+    entry("SYP", 760, Some(2), Some("£"), "Syrian Pound"),
+    entry("SZL", 748, Some(2), Some("L"), "Lilangeni"),
+    entry("THB", 764, Some(2), Some("฿"), "Baht"),
+    entry("TJS", 972, Some(2), Some("SM"), "Somoni"),
+    dated("TMM", 795, Some(2), None, "Turkmenistan Manat", None, Some((2009, 1, 1))),
+    entry("TMT", 934, Some(2), Some("m"), "Turkmenistan New Manat"),
+    entry("TND", 788, Some(3), Some("د.ت"), "Tunisian Dinar"),
+    entry("TOP", 776, Some(2), Some("T$"), "Pa'anga"),
+    dated("TRL", 792, Some(0), Some("TL"), "Old Turkish Lira", None, Some((2005, 1, 1))),
+    dated("TRY", 949, Some(2), Some("₺"), "Turkish Lira", Some((2005, 1, 1)), None),
+    entry("TTD", 780, Some(2), Some("$"), "Trinidad and Tobago Dollar"),
+    entry("TWD", 901, Some(2), Some("$"), "New Taiwan Dollar"),
+    entry("TZS", 834, Some(2), Some("TSh"), "Tanzanian Shilling"),
+    entry("UAH", 980, Some(2), Some("₴"), "Hryvnia"),
+    entry("UGX", 800, Some(0), Some("USh"), "Uganda Shilling"),
+    entry("USD", 840, Some(2), Some("$"), "US Dollar"),
+    entry("USN", 997, Some(2), None, "US Dollar (Next day)"),
+    entry("UYI", 940, Some(0), None, "Uruguay Peso en Unidades Indexadas (UI)"),
+    entry("UYU", 858, Some(2), Some("$"), "Peso Uruguayo"),
+    entry("UYW", 927, Some(4), None, "Unidad Previsional"),
+    entry("UZS", 860, Some(2), Some("soʻm"), "Uzbekistan Sum"),
+    dated("VEB", 862, Some(2), Some("Bs"), "Bolivar", None, Some((2008, 1, 1))),
+    entry("VED", 926, Some(2), Some("Bs.D"), "Bolívar Soberano"),
+    dated("VEF", 937, Some(2), Some("Bs.F"), "Bolívar", None, Some((2018, 8, 20))),
+    dated("VES", 928, Some(2), Some("Bs.S"), "Bolívar Soberano", Some((2018, 8, 20)), None),
+    entry("VND", 704, Some(0), Some("₫"), "Dong"),
+    entry("VUV", 548, Some(0), Some("VT"), "Vatu"),
+    entry("WST", 882, Some(2), Some("T"), "Tala"),
+    entry("XAF", 950, Some(0), Some("FCFA"), "CFA Franc BEAC"),
+    entry("XAG", 961, None, None, "Silver"),
+    entry("XAU", 959, None, None, "Gold"),
+    entry("XBA", 955, None, None, "Bond Markets Unit European Composite Unit (EURCO)"),
+    entry("XBB", 956, None, None, "Bond Markets Unit European Monetary Unit (E.M.U.-6)"),
+    entry("XBC", 957, None, None, "Bond Markets Unit European Unit of Account 9 (E.U.A.-9)"),
+    entry("XBD", 958, None, None, "Bond Markets Unit European Unit of Account 17 (E.U.A.-17)"),
+    entry("XCD", 951, Some(2), Some("$"), "East Caribbean Dollar"),
+    dated("XCG", 532, Some(2), Some("Cg"), "Caribbean Guilder", Some((2025, 3, 31)), None),
+    entry("XDR", 960, None, None, "SDR (Special Drawing Right)"),
+    dated("XEU", 954, None, Some("ECU"), "European Currency Unit (E.C.U.)", None, Some((1999, 1, 1))),
+    entry("XOF", 952, Some(0), Some("F CFA"), "CFA Franc BCEAO"),
+    entry("XPD", 964, None, None, "Palladium"),
+    entry("XPF", 953, Some(0), Some("₣"), "CFP Franc"),
+    entry("XPT", 962, None, None, "Platinum"),
+    entry("XSU", 994, None, None, "Sucre"),
+    entry("XTS", 963, None, None, "Codes specifically reserved for testing purposes"),
+    entry("XUA", 965, None, None, "ADB Unit of Account"),
+    entry("XXX", 999, None, None, "The codes assigned for transactions where no currency is involved"),
+    entry("YER", 886, Some(2), Some("﷼"), "Yemeni Rial"),
+    entry("ZAR", 710, Some(2), Some("R"), "Rand"),
+    dated("ZMK", 894, Some(2), Some("ZK"), "Zambian Kwacha", None, Some((2013, 1, 1))),
+    entry("ZMW", 967, Some(2), Some("ZK"), "Zambian Kwacha"),
+    dated("ZWD", 716, Some(2), Some("Z$"), "Zimbabwe Dollar", None, Some((2008, 8, 1))),
+    dated("ZWG", 924, Some(2), Some("ZiG"), "Zimbabwe Gold", Some((2024, 6, 25)), None),
+    dated("ZWL", 932, Some(2), Some("Z$"), "Zimbabwe Dollar", Some((2009, 2, 2)), Some((2024, 9, 1))),
+];
+
+/// Looks up an alphabetic code, which must already be upper case.
+pub(crate) fn iso4217_entry(code: &str) -> Option<&'static Iso4217Entry> {
+    ISO_4217
+        .binary_search_by(|entry| entry.code.cmp(code))
+        .ok()
+        .map(|i| &ISO_4217[i])
+}
+
+/// Looks up a numeric code, preferring the active currency if the code has
+/// been reused.
+pub(crate) fn iso4217_entry_by_numeric(numeric: u16) -> Option<&'static Iso4217Entry> {
+    ISO_4217
+        .iter()
+        .filter(|entry| entry.numeric == numeric)
+        .min_by_key(|entry| entry.valid_until.is_some())
+}
+
+#[test]
+fn iso4217_table() {
+    assert!(ISO_4217.windows(2).all(|w| w[0].code < w[1].code));
+    assert!(ISO_4217.iter().all(|e| e.code.len() == 3 && e.code.bytes().all(|b| b.is_ascii_uppercase())));
+
+    // Numeric codes are unique among active currencies
+    let active: Vec<_> = ISO_4217.iter().filter(|e| e.valid_until.is_none()).collect();
+    let mut numerics: Vec<u16> = active.iter().map(|e| e.numeric).collect();
+    numerics.sort();
+    numerics.dedup();
+    assert_eq!(numerics.len(), active.len());
+    assert!(ISO_4217.iter().all(|e| match (e.valid_from, e.valid_until) {
+        (Some(from), Some(until)) => from < until,
+        _ => true,
+    }));
+
+    assert_eq!(iso4217_entry("USD").unwrap().numeric, 840);
+    assert_eq!(iso4217_entry_by_numeric(978).unwrap().code, "EUR");
+    assert!(iso4217_entry_by_numeric(0).is_none());
+    assert_eq!(iso4217_entry_by_numeric(532).unwrap().code, "XCG");
+    assert_eq!(iso4217_entry_by_numeric(276).unwrap().code, "DEM");
+    assert_eq!(iso4217_entry("JPY").unwrap().minor_units, Some(0));
+    assert_eq!(iso4217_entry("XAU").unwrap().minor_units, None);
+    assert!(iso4217_entry("usd").is_none());
+    assert!(iso4217_entry("ABC").is_none());
+}
@@ -1,8 +1,37 @@
+pub mod error;
+pub use error::*;
+pub mod rounding_mode;
+pub use rounding_mode::*;
 pub mod decimal_fraction;
 pub use decimal_fraction::*;
+pub mod decimal_format;
+pub use decimal_format::*;
+pub mod big_decimal_fraction;
+pub use big_decimal_fraction::*;
+pub mod big_float;
+pub use big_float::*;
+mod bignum;
 pub mod tags;
 pub use tags::*;
+mod iso4217;
+pub mod currency_definition;
+pub use currency_definition::*;
+pub mod currencies_store;
+pub use currencies_store::*;
 pub mod currency_code;
 pub use currency_code::*;
 pub mod currency_amount;
 pub use currency_amount::*;
+mod cash_rounding;
+pub mod exchange_rate;
+pub use exchange_rate::*;
+pub mod rate_table;
+pub use rate_table::*;
+pub mod money_bag;
+pub use money_bag::*;
+pub mod locale;
+pub use locale::{ Language, Locale };
+pub mod currency_format;
+pub use currency_format::*;
+mod currency_parser;
+mod amount_words;
@@ -0,0 +1,300 @@
+use crate::{ CurrencyCode, Error, Result };
+
+/// A locale with embedded formatting data, taken from a small subset of the
+/// Unicode CLDR.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English (United States): `$1,234.56`
+    EnUs,
+    /// French (Canada): `1 234,56 $`
+    FrCa,
+    /// German (Germany): `1.234,56 €`
+    DeDe,
+}
+
+/// A language with embedded currency names, used by the locales that speak
+/// it and by [`CurrencyAmount::to_words`](crate::CurrencyAmount::to_words).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl From<Locale> for Language {
+    fn from(locale: Locale) -> Self {
+        locale.data().language
+    }
+}
+
+/// Where the currency goes relative to the number in a locale's currency
+/// pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CurrencyPosition {
+    /// `¤#,##0.00`
+    Before,
+    /// `#,##0.00 ¤`, separated by a no-break space.
+    After,
+}
+
+/// The CLDR plural category of a formatted number. Only the categories the
+/// supported languages use for currency amounts are distinguished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PluralCategory {
+    One,
+    Other,
+}
+
+/// The name of a unit in the singular and plural, and whether it is
+/// feminine, which French and Spanish numbers agree with.
+pub(crate) struct Unit {
+    pub one: &'static str,
+    pub other: &'static str,
+    pub feminine: bool,
+}
+
+const fn m(one: &'static str, other: &'static str) -> Unit {
+    Unit { one, other, feminine: false }
+}
+
+const fn f(one: &'static str, other: &'static str) -> Unit {
+    Unit { one, other, feminine: true }
+}
+
+/// A currency's names in one language.
+pub(crate) struct CurrencyNames {
+    pub code: &'static str,
+    /// The CLDR display name, in the singular and plural.
+    pub one: &'static str,
+    pub other: &'static str,
+    /// The major and minor units an amount is spelled out in.
+    pub major: Unit,
+    pub minor: Unit,
+}
+
+const fn names(
+    code: &'static str,
+    one: &'static str,
+    other: &'static str,
+    major: Unit,
+    minor: Unit
+) -> CurrencyNames {
+    CurrencyNames { code, one, other, major, minor }
+}
+
+static ENGLISH_NAMES: &[CurrencyNames] = &[
+    names("AUD", "Australian dollar", "Australian dollars", m("dollar", "dollars"), m("cent", "cents")),
+    names("BHD", "Bahraini dinar", "Bahraini dinars", m("dinar", "dinars"), m("fils", "fils")),
+    names("CAD", "Canadian dollar", "Canadian dollars", m("dollar", "dollars"), m("cent", "cents")),
+    names("CHF", "Swiss franc", "Swiss francs", m("franc", "francs"), m("centime", "centimes")),
+    names("EUR", "euro", "euros", m("euro", "euros"), m("cent", "cents")),
+    names("GBP", "British pound", "British pounds", m("pound", "pounds"), m("penny", "pence")),
+    names("JPY", "Japanese yen", "Japanese yen", m("yen", "yen"), m("sen", "sen")),
+    names("KWD", "Kuwaiti dinar", "Kuwaiti dinars", m("dinar", "dinars"), m("fils", "fils")),
+    names("MXN", "Mexican peso", "Mexican pesos", m("peso", "pesos"), m("centavo", "centavos")),
+    names("USD", "US dollar", "US dollars", m("dollar", "dollars"), m("cent", "cents")),
+];
+
+static FRENCH_NAMES: &[CurrencyNames] = &[
+    names("AUD", "dollar australien", "dollars australiens", m("dollar", "dollars"), m("cent", "cents")),
+    names("BHD", "dinar bahreïni", "dinars bahreïnis", m("dinar", "dinars"), m("fils", "fils")),
+    names("CAD", "dollar canadien", "dollars canadiens", m("dollar", "dollars"), m("cent", "cents")),
+    names("CHF", "franc suisse", "francs suisses", m("franc", "francs"), m("centime", "centimes")),
+    names("EUR", "euro", "euros", m("euro", "euros"), m("centime", "centimes")),
+    names("GBP", "livre sterling", "livres sterling", f("livre", "livres"), m("penny", "pence")),
+    names("JPY", "yen japonais", "yens japonais", m("yen", "yens"), m("sen", "sen")),
+    names("KWD", "dinar koweïtien", "dinars koweïtiens", m("dinar", "dinars"), m("fils", "fils")),
+    names("MXN", "peso mexicain", "pesos mexicains", m("peso", "pesos"), m("centavo", "centavos")),
+    names("USD", "dollar des États-Unis", "dollars des États-Unis", m("dollar", "dollars"), m("cent", "cents")),
+];
+
+static GERMAN_NAMES: &[CurrencyNames] = &[
+    names("AUD", "Australischer Dollar", "Australische Dollar", m("Dollar", "Dollar"), m("Cent", "Cent")),
+    names("BHD", "Bahrain-Dinar", "Bahrain-Dinar", m("Dinar", "Dinar"), m("Fils", "Fils")),
+    names("CAD", "Kanadischer Dollar", "Kanadische Dollar", m("Dollar", "Dollar"), m("Cent", "Cent")),
+    names("CHF", "Schweizer Franken", "Schweizer Franken", m("Franken", "Franken"), m("Rappen", "Rappen")),
+    names("EUR", "Euro", "Euro", m("Euro", "Euro"), m("Cent", "Cent")),
+    names("GBP", "Britisches Pfund", "Britische Pfund", m("Pfund", "Pfund"), m("Penny", "Pence")),
+    names("JPY", "Japanischer Yen", "Japanische Yen", m("Yen", "Yen"), m("Sen", "Sen")),
+    names("KWD", "Kuwait-Dinar", "Kuwait-Dinar", m("Dinar", "Dinar"), m("Fils", "Fils")),
+    names("MXN", "Mexikanischer Peso", "Mexikanische Pesos", m("Peso", "Pesos"), m("Centavo", "Centavos")),
+    names("USD", "US-Dollar", "US-Dollar", m("Dollar", "Dollar"), m("Cent", "Cent")),
+];
+
+static SPANISH_NAMES: &[CurrencyNames] = &[
+    names("AUD", "dólar australiano", "dólares australianos", m("dólar", "dólares"), m("centavo", "centavos")),
+    names("BHD", "dinar bareiní", "dinares bareiníes", m("dinar", "dinares"), m("fils", "fils")),
+    names("CAD", "dólar canadiense", "dólares canadienses", m("dólar", "dólares"), m("centavo", "centavos")),
+    names("CHF", "franco suizo", "francos suizos", m("franco", "francos"), m("céntimo", "céntimos")),
+    names("EUR", "euro", "euros", m("euro", "euros"), m("céntimo", "céntimos")),
+    names("GBP", "libra esterlina", "libras esterlinas", f("libra", "libras"), m("penique", "peniques")),
+    names("JPY", "yen", "yenes", m("yen", "yenes"), m("sen", "sen")),
+    names("KWD", "dinar kuwaití", "dinares kuwaitíes", m("dinar", "dinares"), m("fils", "fils")),
+    names("MXN", "peso mexicano", "pesos mexicanos", m("peso", "pesos"), m("centavo", "centavos")),
+    names("USD", "dólar estadounidense", "dólares estadounidenses", m("dólar", "dólares"), m("centavo", "centavos")),
+];
+
+impl Language {
+    /// The currencies this language has names for, sorted by code.
+    pub(crate) fn currency_names(self) -> &'static [CurrencyNames] {
+        match self {
+            Language::English => ENGLISH_NAMES,
+            Language::French => FRENCH_NAMES,
+            Language::German => GERMAN_NAMES,
+            Language::Spanish => SPANISH_NAMES,
+        }
+    }
+
+    /// The names of `code` in this language, if it has them.
+    pub(crate) fn names(self, code: &str) -> Option<&'static CurrencyNames> {
+        self.currency_names().iter().find(|names| names.code == code)
+    }
+}
+
+pub(crate) struct LocaleData {
+    pub identifier: &'static str,
+    /// The language currency names are taken from.
+    pub language: Language,
+    /// The currency a bare symbol most likely refers to.
+    pub default_currency: &'static str,
+    pub decimal_mark: char,
+    pub grouping_separator: char,
+    pub currency_position: CurrencyPosition,
+    /// Whether the accounting pattern puts negatives in parentheses.
+    pub accounting_parentheses: bool,
+    /// Localized currency symbols, by code.
+    pub symbols: &'static [(&'static str, &'static str)],
+    /// Whether the integer part 0 takes the singular, as in French.
+    pub zero_is_one: bool,
+}
+
+static EN_US: LocaleData = LocaleData {
+    identifier: "en-US",
+    language: Language::English,
+    default_currency: "USD",
+    decimal_mark: '.',
+    grouping_separator: ',',
+    currency_position: CurrencyPosition::Before,
+    accounting_parentheses: true,
+    symbols: &[("CAD", "CA$"), ("EUR", "€"), ("GBP", "£"), ("JPY", "¥"), ("USD", "$")],
+    zero_is_one: false,
+};
+
+static FR_CA: LocaleData = LocaleData {
+    identifier: "fr-CA",
+    language: Language::French,
+    default_currency: "CAD",
+    decimal_mark: ',',
+    grouping_separator: '\u{a0}',
+    currency_position: CurrencyPosition::After,
+    accounting_parentheses: true,
+    symbols: &[("CAD", "$"), ("EUR", "€"), ("GBP", "£"), ("JPY", "¥"), ("USD", "$\u{a0}US")],
+    zero_is_one: true,
+};
+
+static DE_DE: LocaleData = LocaleData {
+    identifier: "de-DE",
+    language: Language::German,
+    default_currency: "EUR",
+    decimal_mark: ',',
+    grouping_separator: '.',
+    currency_position: CurrencyPosition::After,
+    accounting_parentheses: false,
+    symbols: &[("CAD", "CA$"), ("EUR", "€"), ("GBP", "£"), ("JPY", "¥"), ("USD", "$")],
+    zero_is_one: false,
+};
+
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Locale; 3] = [Locale::EnUs, Locale::FrCa, Locale::DeDe];
+
+    /// The BCP 47 identifier, e.g. `"en-US"`.
+    pub fn identifier(self) -> &'static str {
+        self.data().identifier
+    }
+
+    /// The local currency, which an otherwise ambiguous symbol like `$`
+    /// resolves to when parsing.
+    pub fn default_currency(self) -> CurrencyCode {
+        CurrencyCode::new(self.data().default_currency)
+    }
+
+    pub(crate) fn data(self) -> &'static LocaleData {
+        match self {
+            Locale::EnUs => &EN_US,
+            Locale::FrCa => &FR_CA,
+            Locale::DeDe => &DE_DE,
+        }
+    }
+}
+
+impl LocaleData {
+    /// The localized symbol for `code`, if the locale has one.
+    pub fn symbol(&self, code: &str) -> Option<&'static str> {
+        self.symbols.iter().find(|(c, _)| *c == code).map(|(_, symbol)| *symbol)
+    }
+
+    /// The localized name for `code` in the given plural category, if the
+    /// locale's language has one.
+    pub fn name(&self, code: &str, category: PluralCategory) -> Option<&'static str> {
+        self.language.names(code).map(|names| match category {
+            PluralCategory::One => names.one,
+            PluralCategory::Other => names.other,
+        })
+    }
+
+    /// The plural category of a formatted, unsigned number: singular for
+    /// exactly "1" in English and German, and for an integer part of 0 or 1
+    /// in French.
+    pub fn plural_category(&self, integer: &str, fraction: &str) -> PluralCategory {
+        let one = match integer {
+            "1" => self.zero_is_one || fraction.is_empty(),
+            "0" => self.zero_is_one,
+            _ => false,
+        };
+        if one { PluralCategory::One } else { PluralCategory::Other }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = Error;
+
+    /// Parses a BCP 47 identifier such as `"en-US"`, ignoring case and
+    /// accepting `_` as the separator.
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.replace('_', "-");
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.identifier().eq_ignore_ascii_case(&normalized))
+            .ok_or_else(|| Error::UnknownLocale(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.identifier())
+    }
+}
+
+#[test]
+fn locale_parse() {
+    assert_eq!("en-US".parse::<Locale>().unwrap(), Locale::EnUs);
+    assert_eq!("fr_ca".parse::<Locale>().unwrap(), Locale::FrCa);
+    assert_eq!("DE-de".parse::<Locale>().unwrap(), Locale::DeDe);
+    assert_eq!("en-GB".parse::<Locale>(), Err(Error::UnknownLocale("en-GB".into())));
+    assert_eq!(Locale::FrCa.default_currency(), CurrencyCode::new("CAD"));
+    assert_eq!(Language::from(Locale::FrCa), Language::French);
+    for locale in Locale::ALL {
+        assert_eq!(locale.to_string().parse::<Locale>().unwrap(), locale);
+    }
+
+    let fr = Locale::FrCa.data();
+    assert_eq!(fr.plural_category("0", "50"), PluralCategory::One);
+    assert_eq!(fr.plural_category("1", "10"), PluralCategory::One);
+    assert_eq!(fr.plural_category("2", ""), PluralCategory::Other);
+    let en = Locale::EnUs.data();
+    assert_eq!(en.plural_category("1", ""), PluralCategory::One);
+    assert_eq!(en.plural_category("1", "00"), PluralCategory::Other);
+    assert_eq!(en.plural_category("0", ""), PluralCategory::Other);
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use dcbor::prelude::*;
+
+use crate::{ CurrencyAmount, CurrencyCode, DecimalFraction, Result, tags::TAG_MONEY_BAG };
+#[cfg(test)]
+use crate::{ Error, currency_amount::amount, tags::TAG_CURRENCY_CODE };
+
+/// A balance in any number of currencies, such as the contents of a wallet.
+///
+/// Amounts added in the same currency accumulate, and a currency whose
+/// balance reaches zero is removed, so a bag holds at most one nonzero
+/// balance per currency. Balances are kept in their normalized form, so
+/// bags with equal balances are equal and encode to identical bytes:
+/// `33004({currency: amount, ...})`, with the map keys in dCBOR order.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct MoneyBag {
+    balances: BTreeMap<CurrencyCode, DecimalFraction>,
+}
+
+impl MoneyBag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.balances.is_empty()
+    }
+
+    /// The number of currencies with a nonzero balance.
+    pub fn len(&self) -> usize {
+        self.balances.len()
+    }
+
+    /// The balance in `currency`, which is zero if the bag holds none.
+    pub fn balance(&self, currency: &CurrencyCode) -> CurrencyAmount {
+        match self.balances.get(currency) {
+            Some(amount) => CurrencyAmount::new(currency.clone(), *amount),
+            None => CurrencyAmount::zero(currency.clone()),
+        }
+    }
+
+    /// The nonzero balances, sorted by currency code.
+    pub fn amounts(&self) -> impl Iterator<Item = CurrencyAmount> + '_ {
+        self.balances
+            .iter()
+            .map(|(currency, amount)| CurrencyAmount::new(currency.clone(), *amount))
+    }
+
+    /// Adds `amount` to the balance in its currency. On overflow the bag is
+    /// left unchanged.
+    pub fn add(&mut self, amount: &CurrencyAmount) -> Result<()> {
+        let balance = self.balance(amount.currency()).amount().checked_add(*amount.amount())?;
+        self.set(amount.currency(), balance);
+        Ok(())
+    }
+
+    /// Subtracts `amount` from the balance in its currency, which may go
+    /// negative. On overflow the bag is left unchanged.
+    pub fn sub(&mut self, amount: &CurrencyAmount) -> Result<()> {
+        let balance = self.balance(amount.currency()).amount().checked_sub(*amount.amount())?;
+        self.set(amount.currency(), balance);
+        Ok(())
+    }
+
+    fn set(&mut self, currency: &CurrencyCode, balance: DecimalFraction) {
+        if balance.mantissa == 0 {
+            self.balances.remove(currency);
+        } else {
+            self.balances.insert(currency.clone(), balance.normalize());
+        }
+    }
+}
+
+impl std::ops::Add<CurrencyAmount> for MoneyBag {
+    type Output = Result<Self>;
+
+    fn add(mut self, rhs: CurrencyAmount) -> Self::Output {
+        MoneyBag::add(&mut self, &rhs)?;
+        Ok(self)
+    }
+}
+
+impl std::ops::Sub<CurrencyAmount> for MoneyBag {
+    type Output = Result<Self>;
+
+    fn sub(mut self, rhs: CurrencyAmount) -> Self::Output {
+        MoneyBag::sub(&mut self, &rhs)?;
+        Ok(self)
+    }
+}
+
+impl From<MoneyBag> for CBOR {
+    fn from(value: MoneyBag) -> Self {
+        let mut map = Map::new();
+        for (currency, amount) in value.balances {
+            map.insert(currency, amount);
+        }
+        CBOR::to_tagged_value(TAG_MONEY_BAG, map)
+    }
+}
+
+impl TryFrom<CBOR> for MoneyBag {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self, Self::Error> {
+        let item = cbor.try_into_expected_tagged_value(TAG_MONEY_BAG)?;
+        let map = item.try_into_map()?;
+
+        let mut balances = BTreeMap::new();
+        for (key, amount) in map.iter() {
+            let currency: CurrencyCode = key.clone().try_into()?;
+            let amount: DecimalFraction = amount.clone().try_into()?;
+
+            // Only alphabetic keys and nonzero, normalized balances are
+            // deterministic.
+            if *key != currency.to_cbor() {
+                return Err(format!("Balance in {} must be keyed by its alphabetic code", currency).into());
+            }
+            if amount.mantissa == 0 || !amount.is_normalized() {
+                return Err(format!("Balance in {} must be nonzero and normalized", currency).into());
+            }
+            // Map keys are unique, so with alphabetic keys each currency
+            // appears once.
+            balances.insert(currency, amount);
+        }
+
+        Ok(MoneyBag { balances })
+    }
+}
+
+#[test]
+fn money_bag_add_sub() {
+    let strings = |bag: &MoneyBag| bag.amounts().map(|a| a.to_string()).collect::<Vec<_>>();
+
+    let mut bag = MoneyBag::new();
+    bag.add(&amount("USD", "1.10")).unwrap();
+    bag.add(&amount("EUR", "5")).unwrap();
+    bag.add(&amount("USD", "0.90")).unwrap();
+    assert_eq!(strings(&bag), ["EUR 5", "USD 2"]);
+    assert_eq!(bag.balance(&CurrencyCode::new("USD")).to_string(), "USD 2");
+    assert_eq!(bag.balance(&CurrencyCode::new("JPY")).to_string(), "JPY 0");
+
+    // A balance that reaches zero is removed
+    bag.sub(&amount("EUR", "5.00")).unwrap();
+    assert_eq!(strings(&bag), ["USD 2"]);
+    let bag = (bag - amount("GBP", "3.50")).unwrap();
+    assert_eq!(strings(&bag), ["GBP -3.5", "USD 2"]);
+    assert_eq!(bag.len(), 2);
+
+    // Overflow leaves the bag unchanged
+    let mut full = (MoneyBag::new() + CurrencyAmount::new(CurrencyCode::new("USD"), DecimalFraction::new(0, i64::MAX))).unwrap();
+    assert_eq!(full.add(&amount("USD", "1")), Err(Error::MantissaOverflow));
+    assert_eq!(strings(&full), ["USD 9223372036854775807"]);
+    assert!(MoneyBag::new().is_empty());
+}
+
+#[test]
+fn money_bag_cbor() {
+    // Equal balances reached in different ways encode identically
+    let mut a = MoneyBag::new();
+    for s in [("USD", "1.10"), ("EUR", "0.50"), ("JPY", "100")] {
+        a.add(&amount(s.0, s.1)).unwrap();
+    }
+    let mut b = MoneyBag::new();
+    for s in [("JPY", "40"), ("EUR", "0.5"), ("USD", "1.1"), ("JPY", "60"), ("CHF", "1"), ("CHF", "-1.00")] {
+        b.add(&amount(s.0, s.1)).unwrap();
+    }
+    assert_eq!(a, b);
+    assert_eq!(a.to_cbor_data(), b.to_cbor_data());
+
+    let cbor = a.to_cbor();
+    assert_eq!(
+        cbor.diagnostic_flat(),
+        r#"33004({33000("EUR"): 4([-1, 5]), 33000("JPY"): 4([2, 1]), 33000("USD"): 4([-1, 11])})"#
+    );
+    assert_eq!(MoneyBag::try_from(cbor).unwrap(), a);
+    assert_eq!(MoneyBag::try_from(MoneyBag::new().to_cbor()).unwrap(), MoneyBag::new());
+
+    let bag = |entries: Vec<(CBOR, DecimalFraction)>| {
+        let mut map = Map::new();
+        for (currency, amount) in entries {
+            map.insert(currency, amount);
+        }
+        MoneyBag::try_from(CBOR::to_tagged_value(TAG_MONEY_BAG, map))
+    };
+    let usd = CurrencyCode::new("USD").to_cbor();
+    assert!(bag(vec![(usd.clone(), DecimalFraction::new(0, 0))]).is_err());
+    assert!(bag(vec![(usd.clone(), DecimalFraction::new(-2, 110))]).is_err());
+
+    // Numeric keys would give the same bag a second encoding
+    let numeric = CBOR::to_tagged_value(TAG_CURRENCY_CODE, 840);
+    assert!(bag(vec![(numeric.clone(), DecimalFraction::new(0, 1))]).is_err());
+    assert!(bag(vec![(usd, DecimalFraction::new(0, 1)), (numeric, DecimalFraction::new(0, 2))]).is_err());
+}
@@ -0,0 +1,321 @@
+use std::borrow::Cow;
+
+use bc_components::{ Digest, DigestProvider };
+use dcbor::{ Date, prelude::* };
+use num_bigint_dig::BigInt;
+use num_traits::{ ToPrimitive, Zero };
+
+use crate::{
+    CurrencyCode,
+    DecimalFraction,
+    Error,
+    ExchangeRate,
+    Result,
+    RoundingMode,
+    bignum::big_pow10,
+    tags::TAG_RATE_TABLE,
+};
+
+/// A snapshot of exchange rates taken at one point in time.
+///
+/// Rates are kept sorted by currency pair, at most one per pair, so a table
+/// has exactly one encoding: `33003([as_of, [rate, ...]])`. Its
+/// [`digest`](DigestProvider::digest) therefore identifies the snapshot, and
+/// a conversion recorded along with that digest can be reproduced later
+/// from the stored snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateTable {
+    as_of: Date,
+    rates: Vec<ExchangeRate>,
+}
+
+/// How [`RateTable::cross_rate`] arrived at a rate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RatePath {
+    /// The same currency on both sides: a rate of exactly 1.
+    Identity,
+    /// The table's rate for the pair, unchanged.
+    Direct,
+    /// The reciprocal of the table's rate for the reverse pair.
+    Inverse,
+    /// The product of the rates from the base to the `via` currency and from
+    /// `via` to the quote currency.
+    Pivot { via: CurrencyCode, first: Leg, second: Leg },
+}
+
+/// How one leg of a [`RatePath::Pivot`] used the table's rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Leg {
+    /// The table's rate for the leg's pair, as quoted.
+    Direct,
+    /// The reciprocal of the table's rate for the reverse pair.
+    Inverted,
+}
+
+/// A rate computed by [`RateTable::cross_rate`], with the path used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrossRate {
+    rate: ExchangeRate,
+    path: RatePath,
+}
+
+impl CrossRate {
+    pub fn rate(&self) -> &ExchangeRate {
+        &self.rate
+    }
+
+    pub fn path(&self) -> &RatePath {
+        &self.path
+    }
+}
+
+/// Orders rates by currency pair.
+fn pair_key(rate: &ExchangeRate) -> (&str, &str) {
+    (rate.base().code(), rate.quote().code())
+}
+
+/// An exact rational value `numerator / denominator`.
+type Ratio = (BigInt, BigInt);
+
+fn ratio(value: &DecimalFraction) -> Ratio {
+    let mantissa = BigInt::from(value.mantissa);
+    if value.exponent >= 0 {
+        (mantissa * big_pow10(value.exponent as u32), BigInt::from(1))
+    } else {
+        (mantissa, big_pow10(value.exponent.unsigned_abs() as u32))
+    }
+}
+
+impl RateTable {
+    pub fn new(as_of: Date) -> Self {
+        Self { as_of, rates: Vec::new() }
+    }
+
+    pub fn as_of(&self) -> &Date {
+        &self.as_of
+    }
+
+    /// The rates, sorted by base and then quote currency code.
+    pub fn rates(&self) -> &[ExchangeRate] {
+        &self.rates
+    }
+
+    /// Adds a rate, replacing and returning any existing rate for the same
+    /// pair.
+    pub fn insert(&mut self, rate: ExchangeRate) -> Option<ExchangeRate> {
+        match self.rates.binary_search_by(|r| pair_key(r).cmp(&pair_key(&rate))) {
+            Ok(i) => Some(std::mem::replace(&mut self.rates[i], rate)),
+            Err(i) => {
+                self.rates.insert(i, rate);
+                None
+            }
+        }
+    }
+
+    /// The table's rate for exactly this pair, if there is one.
+    pub fn rate(&self, base: &CurrencyCode, quote: &CurrencyCode) -> Option<&ExchangeRate> {
+        self.rates
+            .binary_search_by(|r| pair_key(r).cmp(&(base.code(), quote.code())))
+            .ok()
+            .map(|i| &self.rates[i])
+    }
+
+    /// The exact rate from `base` to `quote` using a direct or inverted rate
+    /// from the table.
+    fn leg(&self, base: &CurrencyCode, quote: &CurrencyCode) -> Option<(Ratio, Leg)> {
+        if let Some(rate) = self.rate(base, quote) {
+            return Some((ratio(rate.rate()), Leg::Direct));
+        }
+        let (numerator, denominator) = ratio(self.rate(quote, base)?.rate());
+        Some(((denominator, numerator), Leg::Inverted))
+    }
+
+    /// Finds the rate from `base` to `quote`: the table's own rate if it has
+    /// one, otherwise the reciprocal of the reverse rate, otherwise the
+    /// product of the rates through `pivot` (e.g. EUR→JPY via USD).
+    ///
+    /// Computed rates are worked out exactly and then rounded once with
+    /// `mode` to `scale` decimal places, and carry the table's `as_of` date.
+    /// A direct rate is returned unchanged, and a currency's rate to itself
+    /// is exactly 1.
+    pub fn cross_rate(
+        &self,
+        base: &CurrencyCode,
+        quote: &CurrencyCode,
+        pivot: &CurrencyCode,
+        scale: i8,
+        mode: RoundingMode
+    ) -> Result<CrossRate> {
+        if base == quote {
+            let rate = ExchangeRate::new(base.clone(), quote.clone(), DecimalFraction::new(0, 1), self.as_of.clone());
+            return Ok(CrossRate { rate, path: RatePath::Identity });
+        }
+        if let Some(rate) = self.rate(base, quote) {
+            return Ok(CrossRate { rate: rate.clone(), path: RatePath::Direct });
+        }
+
+        let ((numerator, denominator), path) = match self.leg(base, quote) {
+            Some((ratio, _)) => (ratio, RatePath::Inverse),
+            None => {
+                let not_found = || Error::NoExchangeRate {
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                };
+                let ((n1, d1), first) = self.leg(base, pivot).ok_or_else(not_found)?;
+                let ((n2, d2), second) = self.leg(pivot, quote).ok_or_else(not_found)?;
+                let path = RatePath::Pivot { via: pivot.clone(), first, second };
+                ((n1 * n2, d1 * d2), path)
+            }
+        };
+        if denominator.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        // value * 10^scale, rounded to an integer, is the mantissa.
+        let (numerator, denominator) = if scale >= 0 {
+            (numerator * big_pow10(scale as u32), denominator)
+        } else {
+            (numerator, denominator * big_pow10(scale.unsigned_abs() as u32))
+        };
+        let mantissa = mode
+            .div_big(&numerator, &denominator)
+            .to_i64()
+            .ok_or(Error::MantissaOverflow)?;
+        let exponent = scale.checked_neg().ok_or(Error::ExponentOverflow)?;
+
+        let rate = ExchangeRate::new(
+            base.clone(),
+            quote.clone(),
+            DecimalFraction::new(exponent, mantissa),
+            self.as_of.clone(),
+        );
+        Ok(CrossRate { rate, path })
+    }
+}
+
+impl DigestProvider for RateTable {
+    fn digest(&self) -> Cow<'_, Digest> {
+        Cow::Owned(Digest::from_image(self.to_cbor_data()))
+    }
+}
+
+impl From<RateTable> for CBOR {
+    fn from(value: RateTable) -> Self {
+        let rates: Vec<CBOR> = value.rates.into_iter().map(CBOR::from).collect();
+        let v = vec![value.as_of.to_cbor(), rates.to_cbor()].to_cbor();
+        CBOR::to_tagged_value(TAG_RATE_TABLE, v)
+    }
+}
+
+impl TryFrom<CBOR> for RateTable {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self, Self::Error> {
+        let item = cbor.try_into_expected_tagged_value(TAG_RATE_TABLE)?;
+        let arr = item.try_into_array()?;
+
+        if arr.len() != 2 {
+            return Err("Expected a two-element array".into());
+        }
+
+        let as_of: Date = arr[0].clone().try_into()?;
+        let rates = arr[1]
+            .clone()
+            .try_into_array()?
+            .into_iter()
+            .map(ExchangeRate::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Only the sorted, duplicate-free form is deterministic.
+        if !rates.windows(2).all(|w| pair_key(&w[0]) < pair_key(&w[1])) {
+            return Err("Rates must be sorted by currency pair with no duplicates".into());
+        }
+
+        Ok(RateTable { as_of, rates })
+    }
+}
+
+#[cfg(test)]
+fn sample_rate_table() -> RateTable {
+    let as_of = Date::from_ymd(2025, 6, 30);
+    let mut table = RateTable::new(as_of.clone());
+    for (base, quote, rate) in [("USD", "JPY", "144.12"), ("EUR", "USD", "1.1787"), ("GBP", "USD", "1.3731")] {
+        let rate = ExchangeRate::new(CurrencyCode::new(base), CurrencyCode::new(quote), rate.parse().unwrap(), as_of.clone());
+        table.insert(rate);
+    }
+    table
+}
+
+#[test]
+fn rate_table_cross_rate() {
+    let table = sample_rate_table();
+    let [eur, usd, jpy, gbp, chf] = ["EUR", "USD", "JPY", "GBP", "CHF"].map(CurrencyCode::new);
+    let cross = |base, quote, scale| table.cross_rate(base, quote, &usd, scale, RoundingMode::HalfEven);
+
+    let direct = cross(&eur, &usd, 2).unwrap();
+    assert_eq!(direct.path(), &RatePath::Direct);
+    assert_eq!(direct.rate().rate().to_string(), "1.1787");
+
+    let inverse = cross(&usd, &eur, 6).unwrap();
+    assert_eq!(inverse.path(), &RatePath::Inverse);
+    assert_eq!(inverse.rate().rate().to_string(), "0.848392"); // 0.8483922965...
+
+    // EUR→JPY is 1.1787 × 144.12 = 169.874244
+    let eur_jpy = cross(&eur, &jpy, 4).unwrap();
+    assert_eq!(eur_jpy.path(), &RatePath::Pivot { via: usd.clone(), first: Leg::Direct, second: Leg::Direct });
+    assert_eq!(eur_jpy.rate().rate().to_string(), "169.8742");
+    assert_eq!(eur_jpy.rate().as_of(), table.as_of());
+
+    // JPY→GBP inverts both legs: 1 / (144.12 × 1.3731)
+    let jpy_gbp = cross(&jpy, &gbp, 8).unwrap();
+    assert_eq!(jpy_gbp.path(), &RatePath::Pivot { via: usd.clone(), first: Leg::Inverted, second: Leg::Inverted });
+    assert_eq!(jpy_gbp.rate().rate().to_string(), "0.00505328");
+
+    // GBP→EUR goes up to USD as quoted, then inverts EUR/USD
+    let gbp_eur = cross(&gbp, &eur, 4).unwrap();
+    assert_eq!(gbp_eur.path(), &RatePath::Pivot { via: usd.clone(), first: Leg::Direct, second: Leg::Inverted });
+    assert_eq!(gbp_eur.rate().rate().to_string(), "1.1649"); // 1.3731 / 1.1787
+
+    // A currency converts to itself at exactly 1, even without any rates
+    let identity = cross(&chf, &chf, 4).unwrap();
+    assert_eq!(identity.path(), &RatePath::Identity);
+    assert_eq!(identity.rate().rate(), &DecimalFraction::new(0, 1));
+    assert_eq!(identity.rate().as_of(), table.as_of());
+    let jpy_gbp = table.cross_rate(&jpy, &gbp, &usd, 8, RoundingMode::Ceiling).unwrap();
+    assert_eq!(jpy_gbp.rate().rate().to_string(), "0.00505329");
+
+    assert_eq!(cross(&eur, &chf, 4), Err(Error::NoExchangeRate { base: "EUR".into(), quote: "CHF".into() }));
+    assert!(table.cross_rate(&eur, &jpy, &chf, 4, RoundingMode::HalfEven).is_err());
+}
+
+#[test]
+fn rate_table_cbor() {
+    let table = sample_rate_table();
+    let pairs: Vec<_> = table.rates().iter().map(|r| format!("{}{}", r.base(), r.quote())).collect();
+    assert_eq!(pairs, ["EURUSD", "GBPUSD", "USDJPY"]);
+
+    // Replacing a rate keeps one per pair
+    let mut updated = table.clone();
+    let old = updated.insert(ExchangeRate::new(
+        CurrencyCode::new("EUR"),
+        CurrencyCode::new("USD"),
+        "1.18".parse().unwrap(),
+        Date::from_ymd(2025, 7, 1),
+    ));
+    assert_eq!(old.unwrap().rate().to_string(), "1.1787");
+    assert_eq!(updated.rates().len(), 3);
+    assert_ne!(updated.digest(), table.digest());
+
+    // A conversion can be reproduced from the stored snapshot
+    let data = table.to_cbor_data();
+    let digest = table.digest().into_owned();
+    let restored = RateTable::try_from(CBOR::try_from_data(&data).unwrap()).unwrap();
+    assert!(digest.validate(&data));
+    assert_eq!(restored.digest().into_owned(), digest);
+    assert_eq!(restored, table);
+
+    let as_of = CBOR::from(Date::from_ymd(2025, 6, 30));
+    let rates: Vec<CBOR> = table.rates().iter().rev().cloned().map(CBOR::from).collect();
+    let unsorted = CBOR::to_tagged_value(TAG_RATE_TABLE, vec![as_of, rates.to_cbor()]);
+    assert!(RateTable::try_from(unsorted).is_err());
+}
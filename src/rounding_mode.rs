@@ -0,0 +1,152 @@
+use num_bigint_dig::BigInt;
+use num_traits::{ Signed, ToPrimitive, Zero };
+
+use crate::bignum::big_pow10;
+
+/// How to round a value that cannot be represented exactly at the requested
+/// scale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to the even neighbor ("banker's rounding").
+    #[default]
+    HalfEven,
+    /// Round to nearest, ties away from zero.
+    HalfUp,
+    /// Round to nearest, ties toward zero.
+    HalfDown,
+    /// Round toward positive infinity.
+    Ceiling,
+    /// Round toward negative infinity.
+    Floor,
+    /// Round toward zero (truncate).
+    TowardZero,
+    /// Round away from zero.
+    AwayFromZero,
+}
+
+impl RoundingMode {
+    /// Divides `n` by `d` and rounds the quotient to an integer using this
+    /// mode. `d` must be nonzero.
+    pub(crate) fn div(self, n: i128, d: i128) -> i128 {
+        let q = n / d;
+        let r = n % d;
+        if r == 0 {
+            return q;
+        }
+
+        let negative = (n < 0) != (d < 0);
+        let away = if negative { q - 1 } else { q + 1 };
+
+        // `|r| < |d| <= 2^127`, so doubling fits in a `u128`.
+        let twice_r = r.unsigned_abs() * 2;
+        let abs_d = d.unsigned_abs();
+
+        let round_away = match self {
+            RoundingMode::HalfEven => twice_r > abs_d || (twice_r == abs_d && q % 2 != 0),
+            RoundingMode::HalfUp => twice_r >= abs_d,
+            RoundingMode::HalfDown => twice_r > abs_d,
+            RoundingMode::Ceiling => !negative,
+            RoundingMode::Floor => negative,
+            RoundingMode::TowardZero => false,
+            RoundingMode::AwayFromZero => true,
+        };
+
+        if round_away { away } else { q }
+    }
+
+    /// Divides `n` by `10^shift` and rounds the quotient to an integer using
+    /// this mode.
+    pub(crate) fn div_pow10(self, n: i128, shift: u32) -> i128 {
+        self.div_scaled(n, 1, shift)
+    }
+
+    /// Divides `n` by `d * 10^shift` and rounds the quotient to an integer
+    /// using this mode. `d` must be nonzero. Falls back to arbitrary
+    /// precision when the divisor doesn't fit in an `i128`.
+    pub(crate) fn div_scaled(self, n: i128, d: i128, shift: u32) -> i128 {
+        match 10i128.checked_pow(shift).and_then(|p| d.checked_mul(p)) {
+            Some(d) => self.div(n, d),
+            None => {
+                let d = BigInt::from(d) * big_pow10(shift);
+                // The divisor's magnitude exceeds 1, so the quotient fits.
+                self.div_big(&BigInt::from(n), &d).to_i128().unwrap()
+            }
+        }
+    }
+
+    /// Like [`RoundingMode::div`], for quotients that need arbitrary
+    /// precision.
+    pub(crate) fn div_big(self, n: &BigInt, d: &BigInt) -> BigInt {
+        let q = n / d;
+        let r = n % d;
+        if r.is_zero() {
+            return q;
+        }
+
+        let negative = n.is_negative() != d.is_negative();
+        let away = if negative { &q - 1 } else { &q + 1 };
+
+        let twice_r = r.abs() * 2;
+        let abs_d = d.abs();
+        let q_is_odd = !(&q % BigInt::from(2)).is_zero();
+
+        let round_away = match self {
+            RoundingMode::HalfEven => twice_r > abs_d || (twice_r == abs_d && q_is_odd),
+            RoundingMode::HalfUp => twice_r >= abs_d,
+            RoundingMode::HalfDown => twice_r > abs_d,
+            RoundingMode::Ceiling => !negative,
+            RoundingMode::Floor => negative,
+            RoundingMode::TowardZero => false,
+            RoundingMode::AwayFromZero => true,
+        };
+
+        if round_away { away } else { q }
+    }
+}
+
+#[test]
+fn rounding_mode_div() {
+    use RoundingMode::*;
+
+    // (numerator, denominator, expected per mode in declaration order)
+    let cases: [(i128, i128, [i128; 7]); 8] = [
+        (25, 10, [2, 3, 2, 3, 2, 2, 3]),
+        (35, 10, [4, 4, 3, 4, 3, 3, 4]),
+        (-25, 10, [-2, -3, -2, -2, -3, -2, -3]),
+        (-35, 10, [-4, -4, -3, -3, -4, -3, -4]),
+        (26, 10, [3, 3, 3, 3, 2, 2, 3]),
+        (24, 10, [2, 2, 2, 3, 2, 2, 3]),
+        (24, -10, [-2, -2, -2, -2, -3, -2, -3]),
+        (20, 10, [2, 2, 2, 2, 2, 2, 2]),
+    ];
+    let modes = [HalfEven, HalfUp, HalfDown, Ceiling, Floor, TowardZero, AwayFromZero];
+
+    for (n, d, expected) in cases {
+        for (mode, expected) in modes.iter().zip(expected) {
+            assert_eq!(mode.div(n, d), expected, "{n} / {d} with {mode:?}");
+            let big = mode.div_big(&BigInt::from(n), &BigInt::from(d));
+            assert_eq!(big, BigInt::from(expected), "{n} / {d} with {mode:?}");
+        }
+    }
+}
+
+#[test]
+fn rounding_mode_div_pow10() {
+    use RoundingMode::*;
+
+    assert_eq!(HalfEven.div_pow10(125, 1), 12);
+    assert_eq!(HalfUp.div_pow10(-125, 1), -13);
+    assert_eq!(HalfEven.div_pow10(125, 0), 125);
+
+    // Divisors beyond 10^38 still round correctly, even for values of more
+    // than half of 10^38.
+    let n = 81 * 10i128.pow(36);
+    let modes = [HalfEven, HalfUp, HalfDown, Ceiling, Floor, TowardZero, AwayFromZero];
+    for (mode, expected) in modes.iter().zip([[0, 0], [0, 0], [0, 0], [1, 0], [0, -1], [0, 0], [1, -1]]) {
+        assert_eq!(mode.div_pow10(n, 39), expected[0], "{mode:?}");
+        assert_eq!(mode.div_pow10(-n, 39), expected[1], "{mode:?}");
+    }
+    assert_eq!(HalfEven.div_scaled(9, i128::MAX, 200), 0);
+    assert_eq!(Ceiling.div_scaled(9, -i128::MAX, 200), 0);
+    assert_eq!(Floor.div_scaled(9, -i128::MAX, 200), -1);
+}
@@ -6,6 +6,13 @@ const_cbor_tag!(33000, CURRENCY_CODE, "CurrencyCode");
 const_cbor_tag!(33001, CURRENCY_AMOUNT, "CurrencyAmount");
 // ANCHOR_END: example_16
 
+const_cbor_tag!(2, POSITIVE_BIGNUM, "PositiveBignum");
+const_cbor_tag!(3, NEGATIVE_BIGNUM, "NegativeBignum");
+const_cbor_tag!(5, BIGFLOAT, "BigFloat");
+const_cbor_tag!(33002, EXCHANGE_RATE, "ExchangeRate");
+const_cbor_tag!(33003, RATE_TABLE, "RateTable");
+const_cbor_tag!(33004, MONEY_BAG, "MoneyBag");
+
 // ANCHOR: example_17
 pub fn register_tags() {
     with_tags_mut!(|tags_store: &mut TagsStore| {
@@ -15,5 +22,23 @@ pub fn register_tags() {
             cbor_tag!(CURRENCY_AMOUNT),
         ]);
     });
+// ANCHOR_END: example_17
+    register_additional_tags();
+// ANCHOR: example_17
 }
 // ANCHOR_END: example_17
+
+/// Registers the tags for the types beyond the book's examples: bignums,
+/// bigfloats, exchange rates, rate tables and money bags.
+fn register_additional_tags() {
+    with_tags_mut!(|tags_store: &mut TagsStore| {
+        tags_store.insert_all(vec![
+            cbor_tag!(POSITIVE_BIGNUM),
+            cbor_tag!(NEGATIVE_BIGNUM),
+            cbor_tag!(BIGFLOAT),
+            cbor_tag!(EXCHANGE_RATE),
+            cbor_tag!(RATE_TABLE),
+            cbor_tag!(MONEY_BAG),
+        ]);
+    });
+}
@@ -0,0 +1,54 @@
+use cbor_book::*;
+use anyhow::Result;
+
+// The currency registry is process-wide, so all assertions that change it
+// live in this single test.
+#[test]
+fn currency_registry() -> Result<()> {
+    assert_eq!(CurrencyCode::try_new("btc"), Err(Error::UnknownCurrencyCode("BTC".into())));
+    assert_eq!(CurrencyCode::new("USDT").definition(), None);
+
+    with_currencies_mut!(|store: &mut CurrenciesStore| {
+        store.insert_all(vec![
+            CurrencyDefinition::new("BTC", None, Some(8), Some("₿"), "Bitcoin"),
+            CurrencyDefinition::new("ETH", None, Some(18), Some("Ξ"), "Ether"),
+            CurrencyDefinition::new("USDT", None, Some(6), None, "Tether"),
+        ])
+    })?;
+
+    // Custom codes validate and carry metadata, and lookups are normalized
+    let btc = CurrencyCode::try_new("btc")?;
+    assert_eq!(btc.code(), "BTC");
+    assert!(btc.is_valid());
+    assert_eq!(btc.minor_units(), Some(8));
+    assert_eq!(btc.symbol().as_deref(), Some("₿"));
+    assert_eq!(CurrencyCode::try_new("USDT")?.name().as_deref(), Some("Tether"));
+    assert_eq!(CurrencyCode::new("ETH").minor_units(), Some(18));
+    assert_eq!(CurrencyCode::new("ETH").numeric_code(), None);
+
+    // 18 minor units, the most allowed, still convert to and from minor units
+    let wei = CurrencyAmount::from_minor_units(CurrencyCode::new("ETH"), 1_500_000_000_000_000_000)?;
+    assert_eq!(wei.to_string(), "ETH 1.500000000000000000");
+    assert_eq!(wei.to_minor_units()?, 1_500_000_000_000_000_000);
+    assert_eq!(wei.round_cash(RoundingMode::HalfEven)?, wei);
+
+    // Registered codes may contain digits
+    let pts2 = CurrencyDefinition::new("PTS2", None, Some(0), None, "Points Series 2");
+    with_currencies_mut!(|store: &mut CurrenciesStore| store.insert(pts2))?;
+    for (s, expected) in [("PTS2 100", "PTS2 100"), ("100 pts2", "PTS2 100"), ("(PTS2 5)", "PTS2 -5"), ("-12.5 PTS2", "PTS2 -12.5")] {
+        assert_eq!(s.parse::<CurrencyAmount>()?.to_string(), expected, "{s}");
+    }
+    assert_eq!(CurrencyAmount::parse_localized("1.234 PTS2", Locale::DeDe)?.to_string(), "PTS2 1234");
+    assert!("PTS2 100 USD".parse::<CurrencyAmount>().is_err());
+
+    // ISO codes can't be redefined
+    let fake_usd = CurrencyDefinition::new("USD", None, Some(0), None, "Fake Dollar");
+    let result = with_currencies_mut!(|store: &mut CurrenciesStore| store.insert(fake_usd));
+    assert_eq!(result, Err(Error::CurrencyCollision("USD".into())));
+    assert_eq!(CurrencyCode::new("USD").minor_units(), Some(2));
+
+    // Unregistered codes are still rejected
+    assert_eq!(CurrencyCode::try_new("DOGE"), Err(Error::UnknownCurrencyCode("DOGE".into())));
+    assert_eq!(CurrencyCode::try_new("ABC"), Err(Error::UnknownCurrencyCode("ABC".into())));
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use cbor_book::*;
+use anyhow::Result;
+use dcbor::prelude::*;
+
+#[test]
+fn register_tags_names() -> Result<()> {
+    register_tags();
+
+    let mut bag = MoneyBag::new();
+    bag.add(&"USD 1.10".parse()?)?;
+    let annotated = bag.to_cbor().diagnostic_annotated();
+    for name in ["MoneyBag", "CurrencyCode", "DecimalFraction"] {
+        assert!(annotated.contains(&format!("/ {} /", name)), "{annotated}");
+    }
+
+    let big: BigDecimalFraction = "1234.567890123456789012".parse()?;
+    let annotated = big.to_cbor().diagnostic_annotated();
+    assert!(annotated.contains("/ PositiveBignum /"), "{annotated}");
+    Ok(())
+}
+
+#[test]
+fn register_tags_bigfloat() {
+    register_tags();
+
+    let value = BigFloat::from_f64(1.5).unwrap();
+    let annotated = value.to_cbor().diagnostic_annotated();
+    assert!(annotated.contains("/ BigFloat /"), "{annotated}");
+}